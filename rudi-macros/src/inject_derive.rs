@@ -0,0 +1,46 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+pub(crate) fn expand(input: TokenStream) -> Result<TokenStream> {
+    let input: DeriveInput = syn::parse2(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let data = match &input.data {
+        Data::Struct(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                name,
+                "Inject can be derived only for struct",
+            ))
+        }
+    };
+
+    let body = match &data.fields {
+        Fields::Named(fields) => {
+            let fields = fields.named.iter().map(|f| {
+                let ident = &f.ident;
+                let ty = &f.ty;
+                quote! { #ident: <#ty as ::rudi::Dependency>::resolve(injector)? }
+            });
+            quote! { #name { #(#fields,)* } }
+        }
+        Fields::Unnamed(fields) => {
+            let fields = fields.unnamed.iter().map(|f| {
+                let ty = &f.ty;
+                quote! { <#ty as ::rudi::Dependency>::resolve(injector)? }
+            });
+            quote! { #name ( #(#fields,)* ) }
+        }
+        Fields::Unit => quote! { #name },
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::rudi::Dependency for #name #ty_generics #where_clause {
+            fn resolve(injector: &::rudi::Injector) -> ::std::result::Result<Self, ::rudi::InjectError> {
+                Ok(#body)
+            }
+        }
+    })
+}
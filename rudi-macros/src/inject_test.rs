@@ -0,0 +1,116 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream, Parser},
+    punctuated::Punctuated,
+    Error, Expr, FnArg, Ident, ItemFn, Result, Token,
+};
+
+#[derive(Default)]
+struct TestArgs {
+    modules: Vec<Expr>,
+    overrides: Vec<Expr>,
+}
+
+impl Parse for TestArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = TestArgs::default();
+
+        while !input.is_empty() {
+            let name: Ident = input.parse()?;
+
+            let content;
+            parenthesized!(content in input);
+            let list = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+
+            match name.to_string().as_str() {
+                "modules" => args.modules.extend(list),
+                "overrides" => args.overrides.extend(list),
+                _ => {
+                    return Err(Error::new(
+                        name.span(),
+                        "expected `modules(..)` or `overrides(..)`",
+                    ))
+                }
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+pub(crate) fn expand(args: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let args = TestArgs::parse.parse2(args)?;
+    let mut f: ItemFn = syn::parse2(item)?;
+
+    let has_test_attr = f.attrs.iter().any(|a| {
+        a.path()
+            .segments
+            .last()
+            .map(|s| s.ident == "test")
+            .unwrap_or(false)
+    });
+
+    if !has_test_attr && f.sig.asyncness.is_some() {
+        return Err(Error::new_spanned(
+            f.sig.fn_token,
+            "async test requires a test attribute of an async runtime, such as #[tokio::test]",
+        ));
+    }
+
+    let mut injects = Vec::new();
+    for arg in f.sig.inputs.iter() {
+        match arg {
+            FnArg::Typed(arg) => {
+                let pat = &arg.pat;
+                let ty = &arg.ty;
+                let name = pat.to_token_stream().to_string();
+                injects.push(quote! {
+                    let #pat: #ty = match <#ty as ::rudi::Dependency>::resolve(&__injector) {
+                        Ok(v) => v,
+                        Err(e) => panic!("failed to inject {}: {}", #name, e),
+                    };
+                });
+            }
+            FnArg::Receiver(r) => {
+                return Err(Error::new_spanned(r, "test function can not take self"));
+            }
+        }
+    }
+    f.sig.inputs = Punctuated::new();
+
+    let modules = &args.modules;
+    let overrides = &args.overrides;
+
+    let block = &f.block;
+    f.block = syn::parse2(quote! {
+        {
+            let __injector = {
+                let mut im = ::rudi::Implements::new();
+                #(
+                    im.add_bind(#modules);
+                )*
+                im.add_bind(::rudi::PriorityModule::new(1, vec![#(std::sync::Arc::new(#overrides),)*]));
+                im.new_injector(vec![])
+            };
+            #(#injects)*
+            #block
+        }
+    })?;
+
+    let test_attr = if has_test_attr {
+        quote! {}
+    } else {
+        quote! { #[::core::prelude::v1::test] }
+    };
+
+    Ok(quote! {
+        #test_attr
+        #f
+    })
+}
@@ -0,0 +1,103 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Error, FnArg, ItemTrait, Pat, PatIdent, Result, TraitItem};
+
+pub(crate) fn expand(args: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    if !args.is_empty() {
+        return Err(Error::new_spanned(args, "interceptable takes no arguments"));
+    }
+    let item: ItemTrait = syn::parse2(item)?;
+
+    if !item.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &item.generics,
+            "interceptable trait can not be generic",
+        ));
+    }
+
+    let vis = &item.vis;
+    let name = &item.ident;
+    let proxy = format_ident!("{}Proxy", name);
+    let trait_name = name.to_string();
+
+    let mut methods = Vec::new();
+    for ti in item.items.iter() {
+        let m = match ti {
+            TraitItem::Fn(m) => m,
+            _ => {
+                return Err(Error::new_spanned(
+                    ti,
+                    "interceptable trait can have only methods",
+                ))
+            }
+        };
+
+        let mut sig = m.sig.clone();
+        match sig.receiver() {
+            Some(r) if r.reference.is_some() && r.mutability.is_none() => {}
+            _ => {
+                return Err(Error::new_spanned(
+                    &m.sig,
+                    "methods of interceptable trait must take &self",
+                ))
+            }
+        }
+
+        let mut args = Vec::new();
+        for (i, arg) in sig.inputs.iter_mut().enumerate() {
+            if let FnArg::Typed(arg) = arg {
+                let ident = format_ident!("__arg{}", i);
+                *arg.pat = Pat::Ident(PatIdent {
+                    attrs: vec![],
+                    by_ref: None,
+                    mutability: None,
+                    ident: ident.clone(),
+                    subpat: None,
+                });
+                args.push(ident);
+            }
+        }
+
+        let method = &sig.ident;
+        let method_name = method.to_string();
+        methods.push(quote! {
+            #sig {
+                let __call = ::rudi::MethodCall::new(
+                    #trait_name,
+                    #method_name,
+                    vec![#(format!("{:?}", #args)),*],
+                );
+                let mut __args = Some((#(#args,)*));
+                let mut __ret = None;
+                ::rudi::invoke_method(&self.interceptors, &__call, &mut || {
+                    let (#(#args,)*) = __args.take().expect("method proceeded more than once");
+                    __ret = Some(self.inner.#method(#(#args),*));
+                });
+                __ret.expect("method interceptor did not proceed")
+            }
+        });
+    }
+
+    Ok(quote! {
+        #item
+
+        /// Proxy passing each method call through the method interceptors.
+        #vis struct #proxy {
+            inner: ::std::sync::Arc<dyn #name>,
+            interceptors: ::std::vec::Vec<::std::sync::Arc<dyn ::rudi::MethodInterceptor>>,
+        }
+
+        impl #name for #proxy {
+            #(#methods)*
+        }
+
+        impl ::rudi::MethodProxy for dyn #name {
+            fn proxy(
+                inner: ::std::sync::Arc<Self>,
+                interceptors: ::std::vec::Vec<::std::sync::Arc<dyn ::rudi::MethodInterceptor>>,
+            ) -> ::std::sync::Arc<Self> {
+                ::std::sync::Arc::new(#proxy { inner, interceptors })
+            }
+        }
+    })
+}
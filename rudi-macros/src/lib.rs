@@ -0,0 +1,62 @@
+use proc_macro::TokenStream;
+
+mod inject_derive;
+mod inject_test;
+mod interceptable;
+
+/// Builds an injector from the listed modules and injects the test function parameters
+/// like constructor arguments, so `Optional<T>`, `Lazy<T>` and `#[derive(Inject)]` structs can be used.
+///
+/// ```ignore
+/// #[rudi::inject_test(modules(HelloModule, BindFunc(default_module)), overrides(BindFunc(mock_module)))]
+/// fn hello_test(hello: Arc<dyn Hello>) {
+///     hello.hello();
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn inject_test(args: TokenStream, item: TokenStream) -> TokenStream {
+    inject_test::expand(args.into(), item.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Implements `Dependency` for a struct by resolving each field, so that the struct can be
+/// a single constructor argument. The struct must not implement `Clone`, which would
+/// make it resolved as a binding of the struct itself.
+///
+/// ```ignore
+/// #[derive(Inject)]
+/// struct Deps {
+///     hello: Arc<dyn Hello>,
+///     injector: Injector,
+///     world: Lazy<Arc<dyn World>>,
+/// }
+/// ```
+#[proc_macro_derive(Inject)]
+pub fn derive_inject(input: TokenStream) -> TokenStream {
+    inject_derive::expand(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Generates `{Trait}Proxy` which passes each method call through `MethodInterceptor`s,
+/// and implements `MethodProxy` for `dyn Trait`. Every method must take `&self`,
+/// and every argument must implement `Debug`.
+///
+/// ```ignore
+/// #[rudi::interceptable]
+/// trait Hello {
+///     fn hello(&self) -> String;
+/// }
+///
+/// intercept_dyn!(binder, Hello).to_method_interceptor(|call: &MethodCall, proceed: &mut dyn FnMut()| {
+///     println!("{}", call);
+///     proceed()
+/// });
+/// ```
+#[proc_macro_attribute]
+pub fn interceptable(args: TokenStream, item: TokenStream) -> TokenStream {
+    interceptable::expand(args.into(), item.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
@@ -1,24 +1,71 @@
 use std::{
-    any::TypeId,
-    collections::HashMap,
+    any::{Any, TypeId},
+    collections::{hash_map::Entry, BTreeMap, HashMap},
+    future::Future,
     marker::PhantomData,
     sync::{Arc, Mutex},
 };
 
+#[cfg(feature = "tokio")]
+use std::time::Duration;
+
+#[cfg(feature = "tokio")]
+use crate::runtime::{merge_services, Service, ServiceKey};
 use crate::{
-    binding::{Binding, InterceptBinding},
+    binding::{Binding, InterceptBinding, RetryPolicy},
+    disposal::CreatedRef,
+    injectable::JitSettings,
+    listener::ProvisionListener,
+    module::Origin,
+    optional_binder::OptionalKey,
     provider::{
-        BoxedIntercept, BoxedProvider, Constructor, ConstructorProvider, InterceptProvider,
-        InterceptProviderAny, Provider, SingletonProvider,
+        AroundProvider, BoxedAround, BoxedIntercept, BoxedProvider, BoxedTryProvider, Constructor,
+        ConstructorProvider, ElementsProvider, FnAround, FnIntercept, FnMatchingAround, FnProvider,
+        InterceptProvider, InterceptProviderAny, Intercepted, Proceed, Provider, SingletonProvider,
+        TryConstructorProvider, TryProvider,
     },
-    Injector, InterceptFunc, ProviderAny,
+    InjectError, Injectable, Injector, InterceptFunc, Jit, MethodInterceptor, MethodProxy,
+    OptionalBinder, ProviderAny, Recorder, TypeMatcher,
 };
 
+type Layer = HashMap<TypeId, Binding>;
+
+/// layer of just-in-time bindings, lower than any other binding.
+pub(crate) const JIT_PRIORITY: i32 = i32::MIN;
+/// layer of the defaults of optional bindings, right above just-in-time bindings.
+pub(crate) const OPTIONAL_DEFAULT_PRIORITY: i32 = i32::MIN + 1;
+/// layer of the `Vec<T>` bindings collecting the elements added by `Binder::multibind`.
+pub(crate) const ELEMENTS_PRIORITY: i32 = i32::MIN + 2;
+/// layers below this priority are reserved for the layers above,
+/// the layers of modules are kept at or above it.
+pub(crate) const MIN_MODULE_PRIORITY: i32 = i32::MIN + 3;
+
+/// priority of layer priority of a binder installed into a binder of priority base.
+/// reserved layers keep their priority.
+fn relative_priority(base: i32, priority: i32) -> i32 {
+    if priority < MIN_MODULE_PRIORITY {
+        priority
+    } else {
+        base.saturating_add(priority).max(MIN_MODULE_PRIORITY)
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct Binder {
-    pub(crate) binds: Arc<Mutex<HashMap<TypeId, Binding>>>,
-    pub(crate) overridable: Arc<Mutex<HashMap<TypeId, Binding>>>,
+    pub(crate) layers: Arc<Mutex<BTreeMap<i32, Layer>>>,
     pub(crate) intercepts: Arc<Mutex<HashMap<TypeId, Vec<InterceptBinding>>>>,
+    // elements of `Vec<T>` added by multibind, by the type id of T
+    pub(crate) elements: Arc<Mutex<HashMap<TypeId, Vec<Binding>>>>,
+    pub(crate) matching_intercepts: Arc<Mutex<Vec<InterceptBinding>>>,
+    pub(crate) optionals: Arc<Mutex<HashMap<TypeId, OptionalKey>>>,
+    pub(crate) jit: Arc<Mutex<JitSettings>>,
+    pub(crate) recorder: Arc<Mutex<Option<Recorder>>>,
+    pub(crate) origin: Option<Origin>,
+    pub(crate) listeners: Arc<Mutex<Vec<Arc<dyn ProvisionListener>>>>,
+    pub(crate) created: CreatedRef,
+    #[cfg(feature = "tokio")]
+    pub(crate) services: Arc<Mutex<Vec<ServiceKey>>>,
+    priority: i32,
 }
 
 impl Binder {
@@ -26,6 +73,24 @@ impl Binder {
         return Binder::default();
     }
 
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// returns a binder sharing the bindings of self, which binds into the layer of given priority.
+    /// for each type, the binding of the highest layer wins.
+    /// the lowest layers are reserved, so priority is raised to the lowest layer of modules.
+    pub fn layer(&self, priority: i32) -> Binder {
+        self.reserved_layer(priority.max(MIN_MODULE_PRIORITY))
+    }
+
+    pub(crate) fn reserved_layer(&self, priority: i32) -> Binder {
+        Binder {
+            priority,
+            ..self.clone()
+        }
+    }
+
     pub fn bind<T>(&self) -> BindTo<T>
     where
         T: 'static,
@@ -35,101 +100,448 @@ impl Binder {
             binder: self.clone(),
             type_id: TypeId::of::<T>(),
             type_name,
+            element: false,
             phantom: PhantomData,
         }
     }
 
+    /// adds an element to the binding of `Vec<T>`, which collects the elements added by every module
+    /// in the order they are added. A binding of `Vec<T>` itself replaces the collected elements.
+    pub fn multibind<T>(&self) -> BindTo<T>
+    where
+        T: 'static + Clone,
+    {
+        let layer = self.reserved_layer(ELEMENTS_PRIORITY);
+        if !layer.contains(TypeId::of::<Vec<T>>()) {
+            layer
+                .bind::<Vec<T>>()
+                .to_provider_dyn(Arc::new(ElementsProvider::<T>(PhantomData)));
+        }
+
+        BindTo {
+            element: true,
+            ..self.bind::<T>()
+        }
+    }
+
     pub fn intercept<T>(&self) -> Intercept<T>
     where
         T: 'static,
     {
-        let type_name = std::any::type_name::<T>().into();
         Intercept {
             binder: self.clone(),
             type_id: TypeId::of::<T>(),
-            type_name,
+            name: None,
+            order: 0,
             phantom: PhantomData,
         }
     }
 
+    /// intercepts every binding whose type name is matched by matcher.
+    pub fn intercept_matching(&self, matcher: TypeMatcher) -> InterceptMatching {
+        InterceptMatching {
+            binder: self.clone(),
+            matcher,
+            name: None,
+            order: 0,
+        }
+    }
+
+    /// binding of T which may be set by any module, otherwise the default if any is used.
+    pub fn optional<T>(&self) -> OptionalBinder<T>
+    where
+        T: 'static,
+    {
+        OptionalBinder::new(self.clone())
+    }
+
+    #[cfg(feature = "tokio")]
+    fn add_service(&self, key: ServiceKey) {
+        merge_services(&mut self.services.lock().unwrap(), vec![key]);
+    }
+
+    /// listener is notified before and after every call to the providers of the injector.
+    pub fn add_provision_listener<L: ProvisionListener + 'static>(&self, listener: L) {
+        self.add_provision_listener_dyn(Arc::new(listener))
+    }
+
+    fn add_provision_listener_dyn(&self, listener: Arc<dyn ProvisionListener>) {
+        let mut l = self.listeners.lock().unwrap();
+        let ptr = Arc::as_ptr(&listener) as *const ();
+        if !l.iter().any(|x| Arc::as_ptr(x) as *const () == ptr) {
+            l.push(listener);
+        }
+    }
+
+    pub(crate) fn provision_listeners(&self) -> Vec<Arc<dyn ProvisionListener>> {
+        self.listeners.lock().unwrap().clone()
+    }
+
+    /// allows `Injector::get_injectable` to create bindings of unbinded `Injectable` types.
+    pub fn enable_jit_bindings(&self) {
+        self.jit.lock().unwrap().enabled = true;
+    }
+
+    /// disables just-in-time bindings, even if enabled by other modules.
+    pub fn require_explicit_bindings(&self) {
+        self.jit.lock().unwrap().explicit = true;
+    }
+
+    pub(crate) fn jit_enabled(&self) -> bool {
+        let jit = self.jit.lock().unwrap();
+        jit.enabled && !jit.explicit
+    }
+
+    pub(crate) fn get_binding(&self, type_id: TypeId) -> Option<Binding> {
+        let layers = self.layers.lock().unwrap();
+
+        layers
+            .values()
+            .rev()
+            .find_map(|layer| layer.get(&type_id))
+            .cloned()
+    }
+
+    /// the winning binding of every type
+    pub(crate) fn get_bindings(&self) -> Vec<Binding> {
+        let layers = self.layers.lock().unwrap();
+
+        let mut winners: HashMap<TypeId, &Binding> = HashMap::new();
+        layers.values().rev().for_each(|layer| {
+            layer.iter().for_each(|(key, b)| {
+                winners.entry(*key).or_insert(b);
+            })
+        });
+
+        winners.into_values().cloned().collect()
+    }
+
     pub(crate) fn get_eager_bindings(&self) -> Vec<Binding> {
-        let m = self.binds.lock().unwrap();
-        let m2 = self.overridable.lock().unwrap();
+        let elements = self.elements.lock().unwrap().clone();
+        self.get_bindings()
+            .into_iter()
+            .chain(elements.into_values().flatten())
+            .filter(|b| b.is_eager)
+            .collect()
+    }
+
+    pub(crate) fn get_elements(&self, type_id: TypeId) -> Vec<Binding> {
+        let m = self.elements.lock().unwrap();
+        m.get(&type_id).cloned().unwrap_or_default()
+    }
+
+    /// adds an element of `Vec<T>`, returns its index.
+    fn add_element(&self, binding: Binding) -> usize {
+        let mut m = self.elements.lock().unwrap();
+        let l = m.entry(binding.type_id).or_default();
+        l.push(binding);
+        l.len() - 1
+    }
+
+    pub(crate) fn max_priority(&self) -> i32 {
+        let layers = self.layers.lock().unwrap();
+        layers.keys().next_back().copied().unwrap_or_default()
+    }
 
-        let i1 = m
+    /// copy of self, sharing the instances of bindings satisfying reuse,
+    /// which are destroyed by the last of self and the copy.
+    // the bindings hold `Box<dyn Any>` instances, which are neither Send nor Sync.
+    #[allow(clippy::arc_with_non_send_sync)]
+    pub(crate) fn derive<F>(&self, reuse: F) -> Binder
+    where
+        F: Fn(&Binding) -> bool,
+    {
+        let mut reused = vec![];
+        let mut copy = |b: &Binding| {
+            if reuse(b) {
+                reused.push(b.clone());
+                b.clone()
+            } else {
+                b.fresh()
+            }
+        };
+
+        let layers = self.layers.lock().unwrap();
+        let layers = layers
             .iter()
-            .map(|t| t.1)
-            .filter(|b| b.is_eager)
-            .map(|b| b.clone());
+            .map(|(priority, layer)| {
+                let layer = layer.iter().map(|(key, b)| (*key, copy(b))).collect();
+                (*priority, layer)
+            })
+            .collect();
 
-        let i2 = m2
+        let elements = self
+            .elements
+            .lock()
+            .unwrap()
             .iter()
-            .map(|t| t.1)
-            .filter(|b| b.is_eager)
-            .map(|b| b.clone());
+            .map(|(key, l)| {
+                let l = l.iter().map(&mut copy).collect();
+                (*key, l)
+            })
+            .collect();
+        let intercepts = self.intercepts.lock().unwrap().clone();
+        let matching_intercepts = self.matching_intercepts.lock().unwrap().clone();
+        let optionals = self.optionals.lock().unwrap().clone();
+        let jit = *self.jit.lock().unwrap();
+        let recorder = self.recorder.lock().unwrap().clone();
+        let listeners = self.listeners.lock().unwrap().clone();
+        #[cfg(feature = "tokio")]
+        let services = self.services.lock().unwrap().clone();
+
+        let created = CreatedRef::default();
+        reused.into_iter().for_each(|b| created.push(b));
+
+        Binder {
+            layers: Arc::new(Mutex::new(layers)),
+            intercepts: Arc::new(Mutex::new(intercepts)),
+            elements: Arc::new(Mutex::new(elements)),
+            matching_intercepts: Arc::new(Mutex::new(matching_intercepts)),
+            optionals: Arc::new(Mutex::new(optionals)),
+            jit: Arc::new(Mutex::new(jit)),
+            recorder: Arc::new(Mutex::new(recorder)),
+            origin: None,
+            listeners: Arc::new(Mutex::new(listeners)),
+            created,
+            #[cfg(feature = "tokio")]
+            services: Arc::new(Mutex::new(services)),
+            priority: self.priority,
+        }
+    }
+
+    /// records the usage of bindings into recorder from the creation of the injector,
+    /// so that eager singletons are recorded, unlike with `Injector::with_recorder`.
+    pub fn set_recorder(&self, recorder: &Recorder) {
+        *self.recorder.lock().unwrap() = Some(recorder.clone());
+    }
+
+    /// panics if an optional binding is set by more than one module in the same layer.
+    pub(crate) fn check_optionals(&self) {
+        let m = self.optionals.lock().unwrap();
+        let mut conflicts: Vec<String> = m
+            .values()
+            .filter(|o| o.sets.values().any(|&n| n > 1))
+            .map(|o| o.type_name.clone())
+            .collect();
+
+        if !conflicts.is_empty() {
+            conflicts.sort();
+            panic!(
+                "conflicting bindings of optional binding {}",
+                conflicts.join(", ")
+            );
+        }
+    }
 
-        i1.chain(i2).collect()
+    /// whether the layer of self has a binding of type_id
+    pub(crate) fn contains(&self, type_id: TypeId) -> bool {
+        let layers = self.layers.lock().unwrap();
+        layers
+            .get(&self.priority)
+            .map(|layer| layer.contains_key(&type_id))
+            .unwrap_or(false)
     }
 
-    pub(crate) fn add_interceptor(&mut self, type_id: TypeId, interceptor: InterceptBinding) {
+    /// inserts binding unless a binding of the same type exists in any layer.
+    pub(crate) fn insert_if_absent(&self, binding: Binding) {
+        let mut layers = self.layers.lock().unwrap();
+
+        if layers.values().any(|l| l.contains_key(&binding.type_id)) {
+            return;
+        }
+        layers
+            .entry(binding.priority)
+            .or_default()
+            .insert(binding.type_id, binding);
+    }
+
+    pub(crate) fn insert(&self, binding: Binding) {
+        let mut layers = self.layers.lock().unwrap();
+        let layer = layers.entry(binding.priority).or_default();
+
+        if layer.contains_key(&binding.type_id) {
+            panic!("duplicated binding {}", binding.type_name());
+        }
+        layer.insert(binding.type_id, binding);
+    }
+
+    /// adds interceptor, replacing the same interceptor or the interceptor of the same name in the same layer.
+    pub(crate) fn add_interceptor(&self, type_id: TypeId, interceptor: InterceptBinding) {
         let mut m = self.intercepts.lock().unwrap();
-        let opt = m.get_mut(&type_id);
-        match opt {
-            Some(list) => {
-                list.push(interceptor);
-            }
-            None => {
-                m.insert(type_id, vec![interceptor]);
-            }
-        };
+        add_or_replace(m.entry(type_id).or_default(), interceptor);
+    }
+
+    pub(crate) fn add_matching_interceptor(&self, interceptor: InterceptBinding) {
+        let mut l = self.matching_intercepts.lock().unwrap();
+        add_or_replace(&mut l, interceptor);
+    }
+
+    /// identity of an interceptor added by the module being configured, if unnamed.
+    fn next_origin(&self, name: &Option<String>) -> Option<(String, usize, usize)> {
+        match (name, &self.origin) {
+            (None, Some(origin)) => Some(origin.next()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn binding_names(&self) -> HashMap<TypeId, String> {
+        self.get_bindings()
+            .into_iter()
+            .map(|b| (b.type_id, b.type_name()))
+            .collect()
     }
 
+    /// installs every binding of other, panics on duplicated binding like `BindTo` does.
+    /// layers of other are relative to the priority of self.
+    pub(crate) fn install(&mut self, other: &Binder) {
+        self.merge_layers(other, true)
+    }
+
+    /// merges every binding of other, keeping the existing binding of the same layer.
+    /// layers of other are relative to the priority of self.
     pub(crate) fn merge(&mut self, other: &Binder) {
+        self.merge_layers(other, false)
+    }
+
+    fn merge_layers(&mut self, other: &Binder, panic_on_duplicate: bool) {
         {
-            let mut this_map = self.binds.lock().unwrap();
-            let other_map = other.binds.lock().unwrap();
-            other_map.iter().for_each(|(key, value)| {
-                if !this_map.contains_key(key) {
-                    this_map.insert(key.clone(), value.clone());
-                }
+            let other_map = other.optionals.lock().unwrap().clone();
+            let mut this_map = self.optionals.lock().unwrap();
+
+            other_map.into_iter().for_each(|(key, value)| {
+                let o = this_map.entry(key).or_insert_with(|| OptionalKey {
+                    type_name: value.type_name.clone(),
+                    ..Default::default()
+                });
+                value.sets.into_iter().for_each(|(priority, n)| {
+                    let sets = o
+                        .sets
+                        .entry(relative_priority(self.priority, priority))
+                        .or_default();
+                    if panic_on_duplicate {
+                        *sets += n;
+                    } else {
+                        *sets = (*sets).max(n);
+                    }
+                });
             });
         }
+        // reported before the bindings of the conflicting modules are found duplicated
+        if panic_on_duplicate {
+            self.check_optionals();
+        }
         {
-            let other_map = other.intercepts.lock().unwrap();
-
-            other_map.iter().for_each(|(key, value)| {
-                value.iter().for_each(|i| {
-                    self.add_interceptor(key.clone(), i.clone());
-                })
+            let other_layers = other.layers.lock().unwrap().clone();
+
+            other_layers.into_iter().for_each(|(priority, layer)| {
+                let priority = relative_priority(self.priority, priority);
+                let mut this_layers = self.layers.lock().unwrap();
+                let this_layer = this_layers.entry(priority).or_default();
+
+                layer
+                    .into_iter()
+                    .for_each(|(key, value)| match this_layer.entry(key) {
+                        Entry::Occupied(_) => {
+                            // the bindings collecting elements are the same in every binder
+                            if panic_on_duplicate && priority != ELEMENTS_PRIORITY {
+                                panic!("duplicated binding {}", value.type_name());
+                            }
+                        }
+                        Entry::Vacant(e) => {
+                            e.insert(value.with_priority(priority));
+                        }
+                    })
             });
         }
-    }
-
-    pub(crate) fn merge_overridable(&mut self, other: &Binder) {
         {
-            let mut this_map = self.overridable.lock().unwrap();
-            let other_map = other.binds.lock().unwrap();
-            other_map.iter().for_each(|(key, value)| {
-                if !this_map.contains_key(key) {
-                    this_map.insert(key.clone(), value.clone());
-                }
-            })
+            let other_map = other.elements.lock().unwrap().clone();
+            let mut this_map = self.elements.lock().unwrap();
+
+            other_map.into_iter().for_each(|(key, value)| {
+                let l = this_map.entry(key).or_default();
+                value.into_iter().for_each(|b| {
+                    let priority = relative_priority(self.priority, b.priority);
+                    l.push(b.with_priority(priority));
+                });
+            });
         }
         {
-            let other_map = other.intercepts.lock().unwrap();
+            let other_map = other.intercepts.lock().unwrap().clone();
 
-            other_map.iter().for_each(|(key, value)| {
-                value.iter().for_each(|i| {
-                    self.add_interceptor(key.clone(), i.clone());
+            other_map.into_iter().for_each(|(key, value)| {
+                value.into_iter().for_each(|mut i| {
+                    i.priority = relative_priority(self.priority, i.priority);
+                    self.add_interceptor(key, i);
                 })
             });
+
+            let other_list = other.matching_intercepts.lock().unwrap().clone();
+            other_list.into_iter().for_each(|mut i| {
+                i.priority = relative_priority(self.priority, i.priority);
+                self.add_matching_interceptor(i);
+            });
+        }
+        if let Some(recorder) = other.recorder.lock().unwrap().clone() {
+            self.set_recorder(&recorder);
+        }
+        {
+            let other_list = other.listeners.lock().unwrap().clone();
+            other_list
+                .into_iter()
+                .for_each(|l| self.add_provision_listener_dyn(l));
+        }
+        {
+            let other_jit = *other.jit.lock().unwrap();
+            let mut this_jit = self.jit.lock().unwrap();
+            this_jit.enabled |= other_jit.enabled;
+            this_jit.explicit |= other_jit.explicit;
+        }
+        #[cfg(feature = "tokio")]
+        {
+            let other_list = other.services.lock().unwrap().clone();
+            merge_services(&mut self.services.lock().unwrap(), other_list);
         }
     }
 
-    pub(crate) fn get_intercepts(&self, type_id: TypeId) -> Vec<InterceptBinding> {
+    #[cfg(feature = "tokio")]
+    pub(crate) fn services(&self) -> Vec<ServiceKey> {
+        self.services.lock().unwrap().clone()
+    }
+
+    /// interceptors of the layers not lower than the priority of the intercepted binding, sorted by order.
+    /// for each name, the interceptor of the highest layer wins.
+    pub(crate) fn get_intercepts(&self, b: &Binding) -> Vec<InterceptBinding> {
         let m = self.intercepts.lock().unwrap();
-        let l = m.get(&type_id);
-        l.map(|x| x.clone()).unwrap_or_default()
+        let g = self.matching_intercepts.lock().unwrap();
+        let l: Vec<&InterceptBinding> = m
+            .get(&b.type_id)
+            .into_iter()
+            .flatten()
+            .chain(g.iter().filter(|i| i.matches(b)))
+            .filter(|i| i.priority >= b.priority)
+            .collect();
+
+        let mut named: HashMap<&str, &InterceptBinding> = HashMap::new();
+        l.iter().for_each(|i| {
+            if let Some(name) = &i.name {
+                let e = named.entry(name).or_insert(i);
+                if e.priority < i.priority {
+                    *e = i;
+                }
+            }
+        });
+
+        let mut ret: Vec<InterceptBinding> = l
+            .iter()
+            .filter(|i| i.provider.is_some())
+            .filter(|i| match &i.name {
+                Some(name) => std::ptr::eq(named[name.as_str()], **i),
+                None => true,
+            })
+            .map(|i| (*i).clone())
+            .collect();
+        ret.sort_by_key(|i| i.order);
+        ret
     }
 }
 
@@ -137,27 +549,138 @@ pub struct BindTo<T: ?Sized> {
     binder: Binder,
     type_id: TypeId,
     type_name: String,
+    // adds an element of `Vec<T>` instead of binding T
+    element: bool,
     phantom: PhantomData<T>,
 }
 
-pub struct BindOption {
+pub struct BindOption<T> {
     binder: Binder,
     type_id: TypeId,
+    // index of the element of `Vec<T>`, if added by multibind
+    element: Option<usize>,
+    phantom: PhantomData<fn(T)>,
 }
 
-impl BindOption {
-    pub fn as_eager(self) -> BindOption {
-        {
-            let mut m = self.binder.binds.lock().unwrap();
-            let b = m.get_mut(&self.type_id);
-            b.into_iter().for_each(|b| b.set_as_eager())
+impl<T: 'static> BindOption<T> {
+    fn update<F: FnOnce(&mut Binding)>(&self, f: F) {
+        if let Some(i) = self.element {
+            let mut m = self.binder.elements.lock().unwrap();
+            if let Some(b) = m.get_mut(&self.type_id).and_then(|l| l.get_mut(i)) {
+                f(b)
+            }
+            return;
         }
+
+        let mut layers = self.binder.layers.lock().unwrap();
+        let b = layers
+            .get_mut(&self.binder.priority)
+            .and_then(|layer| layer.get_mut(&self.type_id));
+        if let Some(b) = b {
+            f(b)
+        }
+    }
+
+    pub fn as_eager(self) -> BindOption<T> {
+        self.update(|b| b.set_as_eager());
+        self
+    }
+
+    /// qualifies the binding by name, which is matched by `TypeMatcher::qualified`.
+    pub fn qualified(self, name: &str) -> BindOption<T> {
+        self.update(|b| b.tags.qualifiers.push(name.into()));
+        self
+    }
+
+    /// marks the binding by M, which is matched by `TypeMatcher::marked`.
+    /// e.g. `marked::<dyn Transactional>()` for the bindings of the implementations of a marker trait.
+    pub fn marked<M: ?Sized + 'static>(self) -> BindOption<T> {
+        self.update(|b| b.tags.markers.push(TypeId::of::<M>()));
+        self
+    }
+
+    /// sets whether the provider is called again after it failed or panicked.
+    pub fn retry(self, policy: RetryPolicy) -> BindOption<T> {
+        self.update(|b| b.retry = policy);
+        self
+    }
+
+    /// marks the binding as a service, started and stopped by `runtime::Runtime`.
+    // consumes the builder like `as_eager`, which it is named after.
+    #[cfg(feature = "tokio")]
+    #[allow(clippy::wrong_self_convention)]
+    pub fn as_service(self) -> BindOption<T>
+    where
+        T: Service + Clone,
+    {
+        self.binder.add_service(ServiceKey::new::<T>(None));
+        self
+    }
+
+    /// marks the binding as a service, which must stop within timeout.
+    #[cfg(feature = "tokio")]
+    #[allow(clippy::wrong_self_convention)]
+    pub fn as_service_with_timeout(self, timeout: Duration) -> BindOption<T>
+    where
+        T: Service + Clone,
+    {
+        self.binder.add_service(ServiceKey::new::<T>(Some(timeout)));
+        self
+    }
+
+    /// f is called after the provider and the interceptors created the instance.
+    pub fn on_init<F>(self, f: F) -> BindOption<T>
+    where
+        F: Fn(&T, &Injector) + Send + Sync + 'static,
+    {
+        self.update(|b| {
+            b.on_init.push(Arc::new(move |ins, injector| {
+                f(ins.downcast_ref::<T>().unwrap(), injector)
+            }))
+        });
+        self
+    }
+
+    /// f is called when the instance is destroyed by `Injector::close` or the drop of the injector,
+    /// in reverse dependency order of the instances.
+    pub fn on_destroy<F>(self, f: F) -> BindOption<T>
+    where
+        F: Fn(&T) + Send + Sync + 'static,
+    {
+        self.update(|b| {
+            b.on_destroy
+                .push(Arc::new(move |ins| f(ins.downcast_ref::<T>().unwrap())))
+        });
+        self
+    }
+
+    /// f is called and awaited when the instance is destroyed by `Injector::close_async`.
+    pub fn on_destroy_async<F, Fut>(self, f: F) -> BindOption<T>
+    where
+        T: Clone,
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.update(|b| {
+            b.on_destroy_async.push(Arc::new(move |ins| {
+                Box::pin(f(ins.downcast_ref::<T>().unwrap().clone()))
+            }))
+        });
         self
     }
 }
 
 impl<T: ?Sized> BindTo<T> {
-    pub fn to_provider_dyn(self, p: Arc<dyn ProviderAny>) -> BindOption
+    /// binder of U, which adds an element of `Vec<U>` if self adds an element.
+    fn rebind<U: 'static + Clone>(&self) -> BindTo<U> {
+        if self.element {
+            self.binder.multibind::<U>()
+        } else {
+            self.binder.bind::<U>()
+        }
+    }
+
+    pub fn to_provider_dyn(self, p: Arc<dyn ProviderAny>) -> BindOption<T>
     where
         T: 'static + Sized,
     {
@@ -165,24 +688,25 @@ impl<T: ?Sized> BindTo<T> {
         let type_name = self.type_name;
         let type_id = self.type_id;
 
-        let prov: Binding = Binding::new(type_id, type_name.clone(), p);
-
-        {
-            let mut m = binder.binds.lock().unwrap();
+        let mut prov: Binding = Binding::new(type_id, type_name, p, binder.priority);
+        prov.tags.module = binder.origin.as_ref().map(|o| o.module().to_string());
 
-            if m.contains_key(&type_id) {
-                panic!("duplicated binding {}", type_name);
-            }
-            m.insert(type_id, prov);
-        }
+        let element = if self.element {
+            Some(binder.add_element(prov))
+        } else {
+            binder.insert(prov);
+            None
+        };
 
         BindOption {
-            binder: binder,
-            type_id: type_id,
+            binder,
+            type_id,
+            element,
+            phantom: PhantomData,
         }
     }
 
-    pub fn to_singleton(self, single: T) -> BindOption
+    pub fn to_singleton(self, single: T) -> BindOption<T>
     where
         T: 'static + Clone,
     {
@@ -193,7 +717,7 @@ impl<T: ?Sized> BindTo<T> {
         self.to_provider_dyn(b)
     }
 
-    pub fn to_constructor<A, C>(self, c: C) -> BindOption
+    pub fn to_constructor<A, C>(self, c: C) -> BindOption<T>
     where
         C: Constructor<A, T> + 'static,
         T: Sized + 'static,
@@ -210,7 +734,63 @@ impl<T: ?Sized> BindTo<T> {
         self.to_provider_dyn(b)
     }
 
-    pub fn to_provider<P>(self, p: P) -> BindOption
+    // consumes the builder like `to_singleton`, which it is named after.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_try_constructor<A, E, C>(self, c: C) -> BindOption<T>
+    where
+        C: Constructor<A, Result<T, E>> + 'static,
+        T: Sized + 'static,
+        E: std::error::Error + Send + Sync + 'static,
+        A: 'static,
+    {
+        let p: TryConstructorProvider<A, T, E, C> = TryConstructorProvider {
+            constructor: c,
+            pa: PhantomData,
+            pt: PhantomData,
+            pe: PhantomData,
+        };
+
+        let b: Arc<dyn ProviderAny> = Arc::new(p);
+
+        self.to_provider_dyn(b)
+    }
+
+    /// binds `Arc<T>` to the value created once by c, so T does not need to be Clone.
+    /// the value is resolved by `Injector::get_shared::<T>()` or a constructor argument `Arc<T>`.
+    // consumes the builder like `to_singleton`, which it is named after.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_shared_constructor<A, C>(self, c: C) -> BindOption<Arc<T>>
+    where
+        C: Constructor<A, T> + 'static,
+        T: Sized + 'static,
+        A: 'static,
+    {
+        self.rebind::<Arc<T>>()
+            .to_constructor(Constructor::map(c, Arc::new))
+    }
+
+    /// binds `Arc<T>` to the given value, so T does not need to be Clone.
+    // consumes the builder like `to_singleton`, which it is named after.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_shared(self, single: T) -> BindOption<Arc<T>>
+    where
+        T: Sized + 'static,
+    {
+        self.rebind::<Arc<T>>().to_singleton(Arc::new(single))
+    }
+
+    /// links T to the binding of I, converted by f.
+    // consumes the builder like `to_singleton`, which it is named after.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_impl_with<I>(self, f: fn(I) -> T) -> BindOption<T>
+    where
+        T: 'static + Sized,
+        I: 'static + Clone,
+    {
+        self.to_constructor(move |i: I| f(i))
+    }
+
+    pub fn to_provider<P>(self, p: P) -> BindOption<T>
     where
         T: 'static + Sized,
         P: Provider<Provided = T> + 'static,
@@ -219,40 +799,200 @@ impl<T: ?Sized> BindTo<T> {
 
         self.to_provider_dyn(b)
     }
+
+    // consumes the builder like `to_singleton`, which it is named after.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_provider_fn<F>(self, f: F) -> BindOption<T>
+    where
+        T: 'static + Sized,
+        F: Fn(&Injector) -> T + Send + Sync + 'static,
+    {
+        self.to_provider(FnProvider { f, pt: PhantomData })
+    }
+
+    // consumes the builder like `to_singleton`, which it is named after.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_try_provider<P>(self, p: P) -> BindOption<T>
+    where
+        T: 'static + Sized,
+        P: TryProvider<Provided = T> + 'static,
+    {
+        let b: Arc<dyn ProviderAny> = Arc::new(BoxedTryProvider(p));
+
+        self.to_provider_dyn(b)
+    }
+}
+
+/// Converts `Arc<I>` into `Arc<Self>`, implemented for trait objects by `impl_upcast!`.
+pub trait Upcast<I> {
+    fn upcast(i: Arc<I>) -> Arc<Self>;
+}
+
+impl<T: ?Sized + 'static> BindTo<Arc<T>> {
+    /// links `Arc<T>` to the binding of `Arc<I>`, so that every trait object linked to I
+    /// shares the instance of I.
+    // consumes the builder like `to_singleton`, which it is named after.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_impl<I>(self) -> BindOption<Arc<T>>
+    where
+        T: Upcast<I>,
+        I: 'static,
+    {
+        self.to_constructor(|i: Arc<I>| T::upcast(i))
+    }
+
+    /// Like `to_impl`, but if `Arc<I>` is not binded and just-in-time bindings are enabled,
+    /// `Arc<I>` is binded to the value created by `I::inject`.
+    // consumes the builder like `to_singleton`, which it is named after.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_injectable_impl<I>(self) -> BindOption<Arc<T>>
+    where
+        T: Upcast<I>,
+        I: Injectable,
+    {
+        self.to_constructor(|i: Jit<Arc<I>>| T::upcast(i.into_inner()))
+    }
 }
 
 pub struct Intercept<T: ?Sized> {
     binder: Binder,
     type_id: TypeId,
-    type_name: String,
+    name: Option<String>,
+    order: i32,
     phantom: PhantomData<T>,
 }
 
 impl<T: 'static> Intercept<T> {
-    fn to_dyn(self, ip: Arc<dyn InterceptProviderAny>) {
-        let mut m = self.binder.intercepts.lock().unwrap();
-        let opt = m.get_mut(&self.type_id);
-        if let Some(l) = opt {
-            l.push(InterceptBinding {
-                type_name: self.type_name,
-                provider: ip,
-            })
-        } else {
-            m.insert(
-                self.type_id,
-                vec![InterceptBinding {
-                    type_name: self.type_name,
-                    provider: ip,
-                }],
-            );
+    /// names the interceptor, so that an interceptor of the same name in a higher layer replaces it.
+    pub fn named(self, name: &str) -> Intercept<T> {
+        Intercept {
+            name: Some(name.into()),
+            ..self
         }
     }
 
+    /// interceptors of lower order are applied first, default is 0.
+    /// interceptors of the same order are applied in the order they were added.
+    pub fn order(self, order: i32) -> Intercept<T> {
+        Intercept { order, ..self }
+    }
+
+    /// removes the interceptor of given name added in lower layers.
+    pub fn remove(self, name: &str) {
+        self.named(name).add(None)
+    }
+
+    fn add(self, provider: Option<Arc<dyn InterceptProviderAny>>) {
+        self.binder.add_interceptor(
+            self.type_id,
+            InterceptBinding {
+                origin: self.binder.next_origin(&self.name),
+                name: self.name,
+                order: self.order,
+                matcher: None,
+                provider,
+                priority: self.binder.priority,
+            },
+        );
+    }
+
+    fn to_dyn(self, ip: Arc<dyn InterceptProviderAny>) {
+        self.add(Some(ip))
+    }
+
     pub fn to<P: 'static + InterceptProvider<Provided = T>>(self, ip: P) {
         self.to_dyn(Arc::new(BoxedIntercept(ip)))
     }
 
+    /// adds an interceptor called instead of the provider, which calls the provider through proceed.
+    // consumes the builder like `to_singleton`, which it is named after.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_around<P: 'static + AroundProvider<Provided = T>>(self, ap: P) {
+        self.to_dyn(Arc::new(BoxedAround(ap)))
+    }
+
+    // consumes the builder like `to_singleton`, which it is named after.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_around_fn<F>(self, f: F)
+    where
+        F: Fn(&Injector, Proceed<T>) -> Result<T, InjectError> + Send + Sync + 'static,
+    {
+        self.to_around(FnAround { f, pt: PhantomData })
+    }
+
     pub fn to_func(self, ip: fn(&Injector, T) -> T) {
         self.to(InterceptFunc(ip))
     }
+
+    // consumes the builder like `to_singleton`, which it is named after.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_fn<F>(self, f: F)
+    where
+        F: Fn(&Injector, T) -> T + Send + Sync + 'static,
+    {
+        self.to(FnIntercept { f, pt: PhantomData })
+    }
+}
+
+fn add_or_replace(list: &mut Vec<InterceptBinding>, interceptor: InterceptBinding) {
+    match list.iter_mut().find(|i| i.is_same(&interceptor)) {
+        Some(i) => *i = interceptor,
+        None => list.push(interceptor),
+    }
+}
+
+impl<T: ?Sized + MethodProxy + 'static> Intercept<Arc<T>> {
+    /// wraps the instance by the proxy generated by `#[rudi::interceptable]`,
+    /// which passes each method call through mi.
+    // consumes the builder like `to_singleton`, which it is named after.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_method_interceptor<M: MethodInterceptor + 'static>(self, mi: M) {
+        let mi: Arc<dyn MethodInterceptor> = Arc::new(mi);
+        self.to_fn(move |_, inner| T::proxy(inner, vec![mi.clone()]))
+    }
+}
+
+/// Interceptor of every binding matched by a `TypeMatcher`.
+pub struct InterceptMatching {
+    binder: Binder,
+    matcher: TypeMatcher,
+    name: Option<String>,
+    order: i32,
+}
+
+impl InterceptMatching {
+    pub fn named(self, name: &str) -> InterceptMatching {
+        InterceptMatching {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+
+    pub fn order(self, order: i32) -> InterceptMatching {
+        InterceptMatching { order, ..self }
+    }
+
+    /// removes the interceptor of given name added in lower layers, for the matched bindings.
+    pub fn remove(self, name: &str) {
+        self.named(name).add(None)
+    }
+
+    fn add(self, provider: Option<Arc<dyn InterceptProviderAny>>) {
+        self.binder.add_matching_interceptor(InterceptBinding {
+            origin: self.binder.next_origin(&self.name),
+            name: self.name,
+            order: self.order,
+            matcher: Some(self.matcher),
+            provider,
+            priority: self.binder.priority,
+        });
+    }
+
+    /// f receives the intercepted binding, and provides the instance through it.
+    pub fn to_around_fn<F>(self, f: F)
+    where
+        F: Fn(&Injector, Intercepted) -> Result<Box<dyn Any>, InjectError> + Send + Sync + 'static,
+    {
+        self.add(Some(Arc::new(FnMatchingAround(f))))
+    }
 }
@@ -1,17 +1,55 @@
 use std::{
     any::{Any, TypeId},
-    sync::{Arc, Mutex},
+    cell::Cell,
+    collections::HashSet,
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    time::{Duration, Instant},
 };
 
-use crate::{provider::InterceptProviderAny, Injector, ProviderAny};
+use crate::{
+    matcher::BindingTags, provider::InterceptProviderAny, BindingStats, InjectError, Injector,
+    ProviderAny, Provision, Record, Scope, TypeMatcher,
+};
+
+/// What happens on the next request after the provider of a binding failed or panicked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RetryPolicy {
+    /// the binding is left uninitialized and the provider is called again
+    #[default]
+    Always,
+    /// the binding is marked failed and returns the same error
+    Never,
+}
+
+type InitHook = Arc<dyn Fn(&dyn Any, &Injector) + Send + Sync>;
+type DestroyHook = Arc<dyn Fn(&dyn Any) + Send + Sync>;
+pub(crate) type DestroyFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type AsyncDestroyHook = Arc<dyn Fn(&dyn Any) -> DestroyFuture + Send + Sync>;
 
 #[derive(Clone)]
 pub(crate) struct Binding {
-    type_id: TypeId,
+    pub(crate) type_id: TypeId,
     type_name: String,
     provider: Arc<dyn ProviderAny>,
     instance: Arc<Mutex<Option<Box<dyn Any>>>>,
+    // number of injectors sharing the instance, the last of which destroys it
+    owners: Arc<AtomicUsize>,
+    failure: Arc<Mutex<Option<InjectError>>>,
+    pub(crate) deps: Arc<Mutex<HashSet<TypeId>>>,
+    pub(crate) stats: Arc<Mutex<BindingStats>>,
+    pub(crate) on_init: Vec<InitHook>,
+    pub(crate) on_destroy: Vec<DestroyHook>,
+    pub(crate) on_destroy_async: Vec<AsyncDestroyHook>,
     pub(crate) is_eager: bool,
+    pub(crate) retry: RetryPolicy,
+    pub(crate) priority: i32,
+    pub(crate) tags: BindingTags,
 }
 
 impl Binding {
@@ -23,64 +61,326 @@ impl Binding {
         type_id: TypeId,
         type_name: String,
         provider: Arc<dyn ProviderAny>,
+        priority: i32,
     ) -> Binding {
         Binding {
+            stats: Arc::new(Mutex::new(BindingStats {
+                type_name: type_name.clone(),
+                ..Default::default()
+            })),
             type_id,
             type_name,
             provider,
             instance: Arc::new(Mutex::new(None)),
+            owners: Default::default(),
+            failure: Default::default(),
+            deps: Default::default(),
+            on_init: vec![],
+            on_destroy: vec![],
+            on_destroy_async: vec![],
             is_eager: false,
+            retry: RetryPolicy::Always,
+            priority,
+            tags: Default::default(),
         }
     }
 
+    /// same binding without the instance created by other injector
+    // the instance is a `Box<dyn Any>`, which is neither Send nor Sync.
+    #[allow(clippy::arc_with_non_send_sync)]
+    pub(crate) fn fresh(&self) -> Binding {
+        Binding {
+            instance: Arc::new(Mutex::new(None)),
+            owners: Default::default(),
+            failure: Default::default(),
+            deps: Default::default(),
+            stats: Arc::new(Mutex::new(BindingStats {
+                type_name: self.type_name(),
+                ..Default::default()
+            })),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn is_initialized(&self) -> bool {
+        self.lock_instance().is_some()
+    }
+
+    // the instance is never left half written, so the lock is usable even if poisoned.
+    fn lock_instance(&self) -> MutexGuard<'_, Option<Box<dyn Any>>> {
+        self.instance.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn depends_on(&self, keys: &HashSet<TypeId>) -> bool {
+        let deps = self.deps.lock().unwrap();
+        deps.iter().any(|d| keys.contains(d))
+    }
+
+    pub(crate) fn with_priority(self, priority: i32) -> Binding {
+        Binding { priority, ..self }
+    }
+
     pub fn type_name(&self) -> String {
         self.type_name.clone()
     }
 
-    pub(crate) fn prepare_instance(&self, injector: &Injector) {
+    pub(crate) fn prepare_instance(&self, injector: &Injector) -> Result<(), InjectError> {
+        // every resolution, including those of the instance already created
+        debug_span!(
+            "resolve",
+            type_name = %self.type_name,
+            path = %injector.loop_checker.visit(self.type_name.clone()).path()
+        );
         if injector.loop_checker.visited.contains(&self.type_name) {
-            panic!("loop detected. path = {}", injector.loop_checker.path());
+            return Err(InjectError::LoopDetected {
+                path: injector.loop_checker.path(),
+            });
         }
 
-        let mut guard = self.instance.lock().unwrap();
+        let wait = Instant::now();
+        let mut guard = self.lock_instance();
+        {
+            let mut stats = self.stats.lock().unwrap();
+            stats.resolutions += 1;
+            stats.lock_wait += wait.elapsed();
+        }
 
         if let Some(_) = guard.as_ref() {
-            return;
+            return Ok(());
         }
 
-        let p = &self.provider;
+        if let Some(e) = self.failure.lock().unwrap().as_ref() {
+            return Err(e.clone());
+        }
+
+        if injector.binds.created.is_closed() {
+            return Err(InjectError::Closed {
+                type_name: self.type_name(),
+                path: injector.loop_checker.path(),
+            });
+        }
 
         let checked = Injector {
-            binds: injector.binds.clone(),
             loop_checker: injector.loop_checker.visit(self.type_name.clone()),
+            deps: Some(self.deps.clone()),
+            ..injector.clone()
         };
+        debug_span!("provide", type_name = %self.type_name, path = %checked.loop_checker.path());
 
-        let ins = p.provide_any(&checked);
+        let provision = Provision {
+            type_name: self.type_name(),
+            scope: if self.is_eager {
+                Scope::EagerSingleton
+            } else {
+                Scope::Singleton
+            },
+            path: checked.loop_checker.path(),
+        };
+        let listeners = injector.binds.provision_listeners();
+        listeners
+            .iter()
+            .for_each(|l| l.before_provision(&provision));
+        let start = Instant::now();
+        let provider_time = Cell::new(Duration::ZERO);
+        let interceptor_time = Cell::new(Duration::ZERO);
 
-        let ic = injector.binds.get_intercepts(self.type_id);
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            injector.record(|| Record::Provided(self.type_name()));
+            let ic = injector.binds.get_intercepts(self);
+            let ins = if ic.is_empty() {
+                self.provider.provide_any(&checked)?
+            } else {
+                let chain = Instant::now();
+                let ins = self.provide_chain(&ic, &checked, &provider_time);
+                interceptor_time.set(chain.elapsed().saturating_sub(provider_time.get()));
+                ins?
+            };
+            self.on_init.iter().for_each(|f| f(ins.as_ref(), &checked));
+            Ok(ins)
+        }))
+        .unwrap_or_else(|panic| {
+            Err(InjectError::ProviderPanicked {
+                type_name: self.type_name(),
+                path: checked.loop_checker.path(),
+                message: panic_message(panic.as_ref()),
+            })
+        });
 
-        let ins = ic
+        let elapsed = start.elapsed();
+        {
+            let mut stats = self.stats.lock().unwrap();
+            stats.constructions += 1;
+            stats.total_construction += elapsed;
+            stats.max_construction = stats.max_construction.max(elapsed);
+            stats.interceptor_time += interceptor_time.get();
+        }
+        listeners
             .iter()
-            .fold(ins, |ins, b| b.provider.intercept_any(&checked, ins));
+            .for_each(|l| l.after_provision(&provision, elapsed, res.as_ref().map(|_| ())));
+
+        let ins = match res {
+            Ok(ins) => ins,
+            Err(e) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %e, "failed to provide {}", self.type_name);
+                if self.retry == RetryPolicy::Never {
+                    *self.failure.lock().unwrap() = Some(e.clone());
+                }
+                return Err(e);
+            }
+        };
 
         *guard = Some(ins);
+        injector.binds.created.push(self.clone());
+        Ok(())
     }
 
-    pub(crate) fn get_instance<T: 'static + Clone>(&self, injector: &Injector) -> T {
-        self.prepare_instance(injector);
-        let guard = self.instance.lock().unwrap();
+    /// shares the instance with one more injector.
+    pub(crate) fn acquire(&self) {
+        self.owners.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// releases the instance from an injector, returns true if it was the last one.
+    fn release(&self) -> bool {
+        self.owners.fetch_sub(1, Ordering::SeqCst) <= 1
+    }
+
+    /// drops the instance after calling the on_destroy hooks, unless another injector shares it.
+    pub(crate) fn destroy(&self) {
+        if !self.release() {
+            return;
+        }
+        let ins = self.lock_instance().take();
+        if let Some(ins) = ins {
+            self.on_destroy.iter().for_each(|f| f(ins.as_ref()));
+        }
+    }
+
+    /// drops the instance after calling the on_destroy hooks,
+    /// and returns the futures of the async ones, which hold a clone of the instance.
+    pub(crate) fn destroy_async(&self) -> Vec<DestroyFuture> {
+        if !self.release() {
+            return vec![];
+        }
+        let ins = self.lock_instance().take();
+        match ins {
+            Some(ins) => {
+                self.on_destroy.iter().for_each(|f| f(ins.as_ref()));
+                self.on_destroy_async
+                    .iter()
+                    .map(|f| f(ins.as_ref()))
+                    .collect()
+            }
+            None => vec![],
+        }
+    }
+
+    /// applies the last interceptor around the rest of the chain, which ends with the provider.
+    fn provide_chain(
+        &self,
+        ic: &[InterceptBinding],
+        injector: &Injector,
+        provider_time: &Cell<Duration>,
+    ) -> Result<Box<dyn Any>, InjectError> {
+        match ic.split_last() {
+            Some((
+                i @ InterceptBinding {
+                    provider: Some(p), ..
+                },
+                rest,
+            )) => {
+                let interceptor = i.label(rest.len());
+                debug_span!("intercept", interceptor = %interceptor);
+                let ins = p.around_any(&self.type_name, injector, &|inj| {
+                    self.provide_chain(rest, inj, provider_time)
+                })?;
+                injector.record(|| Record::Intercepted {
+                    type_name: self.type_name(),
+                    interceptor,
+                });
+                Ok(ins)
+            }
+            Some((_, rest)) => self.provide_chain(rest, injector, provider_time),
+            None => {
+                let start = Instant::now();
+                let ins = self.provider.provide_any(injector);
+                provider_time.set(provider_time.get() + start.elapsed());
+                ins
+            }
+        }
+    }
+
+    pub(crate) fn get_instance<T: 'static + Clone>(
+        &self,
+        injector: &Injector,
+    ) -> Result<T, InjectError> {
+        self.prepare_instance(injector)?;
+        let guard = self.lock_instance();
 
         if let Some(ret) = guard.as_ref() {
-            return ret.downcast_ref::<T>().unwrap().clone();
+            return Ok(ret.downcast_ref::<T>().unwrap().clone());
         } else {
             panic!("impossible");
         }
     }
 }
 
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    if let Some(s) = panic.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = panic.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".into()
+    }
+}
+
 #[derive(Clone)]
-#[allow(dead_code)]
 pub(crate) struct InterceptBinding {
-    pub(crate) type_name: String,
-    pub(crate) provider: Arc<dyn InterceptProviderAny>,
+    pub(crate) name: Option<String>,
+    pub(crate) order: i32,
+    // None for the interceptors of a single type
+    pub(crate) matcher: Option<TypeMatcher>,
+    // None removes the interceptor of the same name in lower layers
+    pub(crate) provider: Option<Arc<dyn InterceptProviderAny>>,
+    // name and address of the module which added the interceptor, and its index in the module,
+    // None outside of modules
+    pub(crate) origin: Option<(String, usize, usize)>,
+    pub(crate) priority: i32,
+}
+
+impl InterceptBinding {
+    pub(crate) fn matches(&self, b: &Binding) -> bool {
+        match &self.matcher {
+            Some(m) => m.matches_tagged(&b.type_name, &b.tags),
+            None => true,
+        }
+    }
+
+    /// name of the interceptor, or its index in the interceptors applied to the binding.
+    pub(crate) fn label(&self, index: usize) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("#{}", index),
+        }
+    }
+
+    /// whether other is the same interceptor merged again, or replaces self in the same layer.
+    /// unnamed interceptors are the same if added by the same module instance at the same index,
+    /// so that installing a module twice applies its interceptors once.
+    pub(crate) fn is_same(&self, other: &InterceptBinding) -> bool {
+        if self.priority != other.priority {
+            return false;
+        }
+        if self.name.is_some() || other.name.is_some() {
+            return self.name == other.name;
+        }
+        if self.origin.is_some() && self.origin == other.origin {
+            return true;
+        }
+        match (&self.provider, &other.provider) {
+            (Some(a), Some(b)) => Arc::as_ptr(a) as *const () == Arc::as_ptr(b) as *const (),
+            _ => false,
+        }
+    }
 }
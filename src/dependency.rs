@@ -0,0 +1,101 @@
+use std::{marker::PhantomData, ops::Deref};
+
+use crate::{InjectError, Injectable, Injector};
+
+/// A constructor argument resolved from the injector.
+///
+/// Implemented for every binded type, and derived for a struct of dependencies by `#[derive(Inject)]`.
+pub trait Dependency: Sized {
+    fn resolve(injector: &Injector) -> Result<Self, InjectError>;
+}
+
+impl<T: 'static + Clone> Dependency for T {
+    fn resolve(injector: &Injector) -> Result<Self, InjectError> {
+        injector.try_get_instance::<T>()
+    }
+}
+
+/// Constructor argument which is None if T is not binded.
+///
+/// `Option<T>` can not be used for this, since it is resolved as a binding of `Option<T>` itself.
+#[derive(Debug)]
+pub struct Optional<T>(pub Option<T>);
+
+impl<T> Optional<T> {
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for Optional<T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> From<Optional<T>> for Option<T> {
+    fn from(o: Optional<T>) -> Self {
+        o.0
+    }
+}
+
+impl<T: 'static + Clone> Dependency for Optional<T> {
+    fn resolve(injector: &Injector) -> Result<Self, InjectError> {
+        injector.try_get_optional::<T>().map(Optional)
+    }
+}
+
+/// Constructor argument resolved by `Injector::try_get_injectable`, so that an unbinded
+/// `Injectable` T gets a just-in-time binding if enabled. A plain argument T never does.
+#[derive(Debug)]
+pub struct Jit<T>(pub T);
+
+impl<T> Jit<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Jit<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Injectable + Clone> Dependency for Jit<T> {
+    fn resolve(injector: &Injector) -> Result<Self, InjectError> {
+        injector.try_get_injectable::<T>().map(Jit)
+    }
+}
+
+/// Constructor argument resolved when `get` is called, which allows cyclic dependencies.
+/// It does not keep the instances alive, so `get` fails with `InjectError::Closed` once they are destroyed.
+pub struct Lazy<T> {
+    injector: Injector,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: 'static + Clone> Lazy<T> {
+    pub fn get(&self) -> T {
+        self.try_get().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_get(&self) -> Result<T, InjectError> {
+        self.injector.try_get_instance::<T>()
+    }
+}
+
+impl<T: 'static + Clone> Dependency for Lazy<T> {
+    fn resolve(injector: &Injector) -> Result<Self, InjectError> {
+        injector.record_dependency::<T>();
+
+        Ok(Lazy {
+            injector: injector.downgrade(),
+            phantom: PhantomData,
+        })
+    }
+}
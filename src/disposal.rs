@@ -0,0 +1,124 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex, Weak,
+};
+
+use crate::binding::{Binding, DestroyFuture};
+
+/// Bindings whose instance is created by an injector, destroyed when the last binder sharing them is dropped.
+#[derive(Default)]
+pub(crate) struct Created {
+    bindings: Mutex<Vec<Binding>>,
+    // set by the first close, no instance is created after it
+    closed: AtomicBool,
+}
+
+impl Created {
+    pub(crate) fn push(&self, binding: Binding) {
+        binding.acquire();
+        self.bindings.lock().unwrap().push(binding);
+    }
+
+    pub(crate) fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// takes the next binding in reverse dependency order, a binding no remaining binding depends on.
+    /// bindings in a dependency cycle are taken in reverse creation order.
+    pub(crate) fn take_next(&self) -> Option<Binding> {
+        let mut created = self.bindings.lock().unwrap();
+        if created.is_empty() {
+            return None;
+        }
+
+        let i = (0..created.len())
+            .rev()
+            .find(|&i| {
+                let key = created[i].type_id;
+                !created
+                    .iter()
+                    .any(|b| b.type_id != key && b.deps.lock().unwrap().contains(&key))
+            })
+            .unwrap_or(created.len() - 1);
+        Some(created.remove(i))
+    }
+
+    /// destroys the next binding, returning the futures of its async hooks.
+    pub(crate) fn destroy_next_async(&self) -> Option<Vec<DestroyFuture>> {
+        self.take_next().map(|b| b.destroy_async())
+    }
+
+    pub(crate) fn destroy(&self) {
+        self.close();
+        while let Some(b) = self.take_next() {
+            b.destroy()
+        }
+    }
+}
+
+impl Drop for Created {
+    fn drop(&mut self) {
+        self.destroy()
+    }
+}
+
+/// Handle of the bindings created by an injector, held weakly by the injectors of instances,
+/// such as those of `Lazy` or `Injector` arguments, so that an instance does not keep itself alive.
+#[derive(Clone)]
+pub(crate) enum CreatedRef {
+    Strong(Arc<Created>),
+    Weak(Weak<Created>),
+}
+
+impl Default for CreatedRef {
+    fn default() -> Self {
+        CreatedRef::Strong(Default::default())
+    }
+}
+
+impl CreatedRef {
+    pub(crate) fn downgrade(&self) -> CreatedRef {
+        match self {
+            CreatedRef::Strong(c) => CreatedRef::Weak(Arc::downgrade(c)),
+            CreatedRef::Weak(c) => CreatedRef::Weak(c.clone()),
+        }
+    }
+
+    fn get(&self) -> Option<Arc<Created>> {
+        match self {
+            CreatedRef::Strong(c) => Some(c.clone()),
+            CreatedRef::Weak(c) => c.upgrade(),
+        }
+    }
+
+    pub(crate) fn push(&self, binding: Binding) {
+        if let Some(c) = self.get() {
+            c.push(binding)
+        }
+    }
+
+    pub(crate) fn close(&self) {
+        if let Some(c) = self.get() {
+            c.close()
+        }
+    }
+
+    /// the bindings are closed, or already destroyed by the drop of the last injector.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.get().is_none_or(|c| c.is_closed())
+    }
+
+    pub(crate) fn destroy_next_async(&self) -> Option<Vec<DestroyFuture>> {
+        self.get().and_then(|c| c.destroy_next_async())
+    }
+
+    pub(crate) fn destroy(&self) {
+        if let Some(c) = self.get() {
+            c.destroy()
+        }
+    }
+}
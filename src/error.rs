@@ -0,0 +1,87 @@
+use std::{error::Error, fmt::Display, sync::Arc};
+
+use crate::Injector;
+
+#[derive(Clone, Debug)]
+pub enum InjectError {
+    NotBinded {
+        type_name: String,
+        path: String,
+    },
+    LoopDetected {
+        path: String,
+    },
+    ProviderFailed {
+        type_name: String,
+        path: String,
+        source: Arc<dyn Error + Send + Sync>,
+    },
+    ProviderPanicked {
+        type_name: String,
+        path: String,
+        message: String,
+    },
+    /// the injector was closed before the instance was created
+    Closed {
+        type_name: String,
+        path: String,
+    },
+}
+
+impl InjectError {
+    pub(crate) fn provider_failed<T, E>(injector: &Injector, e: E) -> InjectError
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        InjectError::ProviderFailed {
+            type_name: std::any::type_name::<T>().into(),
+            path: injector.loop_checker.path(),
+            source: Arc::new(e),
+        }
+    }
+}
+
+impl Display for InjectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InjectError::NotBinded { type_name, path } => {
+                write!(f, "type {} not binded. path = {}", type_name, path)
+            }
+            InjectError::LoopDetected { path } => write!(f, "loop detected. path = {}", path),
+            InjectError::ProviderFailed {
+                type_name,
+                path,
+                source,
+            } => write!(
+                f,
+                "failed to provide {}: {}. path = {}",
+                type_name, source, path
+            ),
+            InjectError::ProviderPanicked {
+                type_name,
+                path,
+                message,
+            } => write!(
+                f,
+                "provider of {} panicked: {}. path = {}",
+                type_name, message, path
+            ),
+            InjectError::Closed { type_name, path } => {
+                write!(
+                    f,
+                    "injector closed, {} not provided. path = {}",
+                    type_name, path
+                )
+            }
+        }
+    }
+}
+
+impl Error for InjectError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InjectError::ProviderFailed { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, sync::Arc};
 
-use crate::{AbstractModule, Binder, Injector};
+use crate::{module::configure, AbstractModule, Binder, Injector};
 
 #[derive(Default, Clone)]
 pub struct Implements {
@@ -40,32 +40,25 @@ impl Implements {
     }
 
     pub fn new_injector(&self, enabled: Vec<String>) -> Injector {
+        debug_span!("new_injector", implements = ?enabled);
         let mut binder = Binder::new();
 
         for m in &self.anonymous_module {
-            m.config(&mut binder);
+            configure(m.as_ref(), &mut binder);
         }
 
         enabled.iter().for_each(|name| {
             if let Some(module) = self.named_module.get(name) {
-                module.config(&mut binder);
+                configure(module.as_ref(), &mut binder);
             } else {
                 panic!("module {} not exists", name);
             }
         });
 
-        let ret = Injector {
-            binds: binder,
-            loop_checker: Default::default(),
-        };
-
-        let eager = ret.binds.get_eager_bindings();
-
-        eager.into_iter().for_each(|b| {
-            println!("start eager singleton {}", b.type_name());
-            b.prepare_instance(&ret)
-        });
+        binder.check_optionals();
 
+        let ret = Injector::new(binder);
+        ret.prepare_eager();
         ret
     }
 }
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::Injector;
+
+/// Type which creates itself from the injector.
+///
+/// If just-in-time bindings are enabled by `Binder::enable_jit_bindings`,
+/// `Injector::get_injectable` and constructor arguments `Jit<T>` create and cache
+/// a binding of an unbinded `Injectable` type. `get_instance` and plain arguments T do not.
+pub trait Injectable: Sized + 'static {
+    fn inject(injector: &Injector) -> Self;
+}
+
+/// shares a value of a type which is not Clone.
+impl<T: Injectable> Injectable for Arc<T> {
+    fn inject(injector: &Injector) -> Self {
+        Arc::new(T::inject(injector))
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct JitSettings {
+    pub(crate) enabled: bool,
+    pub(crate) explicit: bool,
+}
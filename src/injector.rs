@@ -1,6 +1,18 @@
-use std::{any::TypeId, collections::HashSet};
+use std::{
+    any::{Any, TypeId},
+    collections::HashSet,
+    future::Future,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
 
-use crate::{binding::Binding, provider::Constructor, Binder};
+use crate::{
+    binder::JIT_PRIORITY,
+    binding::Binding,
+    module::configure,
+    provider::{BoxedProvider, Constructor, FnProvider},
+    AbstractModule, Binder, BindingStats, InjectError, Injectable, Record, Recorder,
+};
 
 #[derive(Clone, Default)]
 pub(crate) struct LoopChecker {
@@ -26,41 +38,157 @@ impl LoopChecker {
 pub struct Injector {
     pub(crate) binds: Binder,
     pub(crate) loop_checker: LoopChecker,
+    // dependencies of the binding being provided
+    pub(crate) deps: Option<Arc<Mutex<HashSet<TypeId>>>>,
+    pub(crate) recorder: Option<Recorder>,
 }
 
 unsafe impl Send for Injector {}
 
 impl Injector {
-    fn get_overridable_bind<T: 'static>(&self) -> Option<Binding> {
-        let typeid = TypeId::of::<T>();
+    pub(crate) fn new(binds: Binder) -> Injector {
+        let recorder = binds.recorder.lock().unwrap().clone();
+        Injector {
+            binds,
+            loop_checker: Default::default(),
+            deps: None,
+            recorder,
+        }
+    }
 
-        let binder = self.binds.overridable.lock().unwrap();
+    /// the injector outside of any resolution
+    pub(crate) fn detach(&self) -> Injector {
+        Injector {
+            loop_checker: Default::default(),
+            deps: None,
+            ..self.clone()
+        }
+    }
 
-        let bind = binder.get(&typeid);
+    /// the injector held by an instance, which does not keep the instances alive,
+    /// so that dropping the last other injector destroys them.
+    pub(crate) fn downgrade(&self) -> Injector {
+        let mut injector = self.detach();
+        injector.binds.created = injector.binds.created.downgrade();
+        injector
+    }
 
-        bind.map(|x| x.clone())
-        //bind.and_then(|p| p.downcast::<T>().clone())
+    /// Returns an injector recording the usage of bindings into recorder.
+    /// Instances created before, such as eager singletons, are not recorded,
+    /// unless the recorder is set by `Binder::set_recorder`.
+    pub fn with_recorder(&self, recorder: &Recorder) -> Injector {
+        Injector {
+            recorder: Some(recorder.clone()),
+            ..self.clone()
+        }
     }
 
-    fn get_bind<T: 'static>(&self) -> Option<Binding> {
-        let typeid = TypeId::of::<T>();
+    pub(crate) fn record<F: FnOnce() -> Record>(&self, f: F) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record(f());
+        }
+    }
 
-        let binder = self.binds.binds.lock().unwrap();
+    pub(crate) fn prepare_eager(&self) {
+        let eager = self.binds.get_eager_bindings();
 
-        let bind = binder.get(&typeid);
+        eager.into_iter().for_each(|b| {
+            debug_span!("eager", type_name = %b.type_name());
+            self.record(|| Record::Resolved(b.type_name()));
+            b.prepare_instance(self).unwrap_or_else(|e| panic!("{}", e))
+        });
+    }
 
-        bind.map(|x| x.clone())
-            .or_else(|| self.get_overridable_bind::<T>())
-        //bind.and_then(|p| p.downcast::<T>().clone())
+    fn get_bind<T: 'static>(&self) -> Option<Binding> {
+        self.binds.get_binding(TypeId::of::<T>())
     }
 
+    pub(crate) fn record_dependency<T: 'static>(&self) {
+        if let Some(deps) = &self.deps {
+            deps.lock().unwrap().insert(TypeId::of::<T>());
+        }
+        self.record(|| Record::Resolved(std::any::type_name::<T>().into()));
+    }
+
+    /// returns None if T is not binded, panics if T fails to be provided.
     pub fn get_instance<T>(&self) -> Option<T>
     where
         T: 'static + Clone,
     {
-        let b = self.get_bind::<T>();
+        self.try_get_optional::<T>()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        b.map(|x| x.get_instance::<T>(self))
+    pub fn try_get_instance<T>(&self) -> Result<T, InjectError>
+    where
+        T: 'static + Clone,
+    {
+        self.try_get_optional::<T>()?
+            .ok_or_else(|| InjectError::NotBinded {
+                type_name: std::any::type_name::<T>().into(),
+                path: self.loop_checker.path(),
+            })
+    }
+
+    /// returns Ok(None) if T is not binded.
+    /// the injector itself is always available as `Injector`. Provided to a constructor, it does not
+    /// keep the instances alive, and fails with `InjectError::Closed` once they are destroyed.
+    pub fn try_get_optional<T>(&self) -> Result<Option<T>, InjectError>
+    where
+        T: 'static + Clone,
+    {
+        if TypeId::of::<T>() == TypeId::of::<Injector>() {
+            // inside a provider, the injector may be held by the instance
+            let this: Box<dyn Any> = match self.deps {
+                Some(_) => Box::new(self.downgrade()),
+                None => Box::new(self.clone()),
+            };
+            return Ok(this.downcast::<T>().ok().map(|x| *x));
+        }
+
+        self.record_dependency::<T>();
+
+        match self.get_bind::<T>() {
+            Some(b) => b.get_instance::<T>(self).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// returns the value binded by `to_shared_constructor` or `to_shared`, without cloning T.
+    pub fn get_shared<T: 'static>(&self) -> Option<Arc<T>> {
+        self.get_instance::<Arc<T>>()
+    }
+
+    pub fn try_get_shared<T: 'static>(&self) -> Result<Arc<T>, InjectError> {
+        self.try_get_instance::<Arc<T>>()
+    }
+
+    /// Like `get_instance`, but creates a binding of an unbinded T if just-in-time bindings are enabled.
+    pub fn get_injectable<T: Injectable + Clone>(&self) -> Option<T> {
+        self.prepare_jit::<T>();
+        self.get_instance::<T>()
+    }
+
+    pub fn try_get_injectable<T: Injectable + Clone>(&self) -> Result<T, InjectError> {
+        self.prepare_jit::<T>();
+        self.try_get_instance::<T>()
+    }
+
+    fn prepare_jit<T: Injectable>(&self) {
+        if self.binds.jit_enabled() && self.get_bind::<T>().is_none() {
+            let provider = BoxedProvider {
+                p: FnProvider {
+                    f: T::inject,
+                    pt: PhantomData,
+                },
+            };
+            self.binds.insert_if_absent(Binding::new(
+                TypeId::of::<T>(),
+                std::any::type_name::<T>().into(),
+                Arc::new(provider),
+                JIT_PRIORITY,
+            ));
+        }
     }
 
     pub fn inject_and_call<A, R, C>(&self, c: C) -> R
@@ -69,4 +197,104 @@ impl Injector {
     {
         c.new(self)
     }
+
+    pub fn try_inject_and_call<A, R, C>(&self, c: C) -> Result<R, InjectError>
+    where
+        C: Constructor<A, R>,
+    {
+        c.try_new(self)
+    }
+
+    /// destroys the instances created by this injector in reverse dependency order,
+    /// calling their on_destroy hooks. After close, resolving a binding whose instance is not
+    /// created yet, or was destroyed, fails with `InjectError::Closed`.
+    /// Dropping the last clone of the injector closes it as well, the clones held by instances aside.
+    /// Async hooks are called only by `close_async`.
+    pub fn close(&self) {
+        self.binds.created.destroy()
+    }
+
+    /// Like `close`, but awaits the on_destroy_async hooks of each instance before destroying the next.
+    /// The instances are taken when the future is polled, so the future is Send.
+    pub fn close_async(&self) -> impl Future<Output = ()> + Send + 'static {
+        let injector = self.detach();
+        async move {
+            // moved as a whole, the bindings are Send only through the injector
+            let injector = injector;
+            injector.binds.created.close();
+            loop {
+                // a statement of its own, so that no borrow of the bindings is held across await
+                let destroys = injector.binds.created.destroy_next_async();
+                match destroys {
+                    Some(destroys) => {
+                        for f in destroys {
+                            f.await
+                        }
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+
+    /// statistics of the bindings, sorted by type name.
+    pub fn stats(&self) -> Vec<BindingStats> {
+        let mut ret: Vec<BindingStats> = self
+            .binds
+            .get_bindings()
+            .iter()
+            .map(|b| b.stats.lock().unwrap().clone())
+            .collect();
+        ret.sort_by(|a, b| a.type_name.cmp(&b.type_name));
+        ret
+    }
+
+    /// Creates an injector whose bindings are replaced by the bindings of module.
+    /// Singletons already created by self are reused, unless they depend on a replaced binding,
+    /// and destroyed by whichever of both injectors is closed or dropped last.
+    /// Panics if module sets an optional binding more than once, like `Implements::new_injector`.
+    pub fn with_overrides<M: AbstractModule>(&self, module: M) -> Injector {
+        debug_span!("with_overrides", module = %module.name());
+        let mut ov = Binder::new();
+        configure(&module, &mut ov);
+        ov.check_optionals();
+
+        let mut replaced: HashSet<TypeId> = ov.binding_names().into_keys().collect();
+        replaced.extend(ov.intercepts.lock().unwrap().keys());
+
+        let bindings = self.binds.get_bindings();
+        let matching = ov.matching_intercepts.lock().unwrap().clone();
+        replaced.extend(
+            bindings
+                .iter()
+                .filter(|b| matching.iter().any(|i| i.matches(b)))
+                .map(|b| b.type_id),
+        );
+        loop {
+            let dependents: Vec<TypeId> = bindings
+                .iter()
+                .filter(|b| !replaced.contains(&b.type_id) && b.depends_on(&replaced))
+                .map(|b| b.type_id)
+                .collect();
+
+            if dependents.is_empty() {
+                break;
+            }
+            replaced.extend(dependents);
+        }
+
+        let binder = self
+            .binds
+            .derive(|b| b.is_initialized() && !replaced.contains(&b.type_id));
+
+        let top = binder.max_priority().saturating_add(1);
+        binder.layer(top).install(&ov);
+
+        let mut ret = Injector::new(binder);
+        if ret.recorder.is_none() {
+            ret.recorder = self.recorder.clone();
+        }
+        ret.prepare_eager();
+        ret
+    }
 }
@@ -1,25 +1,76 @@
+// enters a debug span until the end of the enclosing block, if the tracing feature is enabled.
+macro_rules! debug_span {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($($arg)*).entered();
+    };
+}
+
 mod binder;
 mod binding;
+mod dependency;
+mod disposal;
+mod error;
 mod implements;
+mod injectable;
 mod injector;
+mod listener;
+mod matcher;
+mod method;
 mod module;
+mod optional_binder;
 mod provider;
+mod recorder;
+#[cfg(feature = "tokio")]
+pub mod runtime;
+mod stats;
 
 pub use binder::Binder;
+pub use binder::InterceptMatching;
+pub use binder::Upcast;
+pub use binding::RetryPolicy;
+pub use dependency::Dependency;
+pub use dependency::Jit;
+pub use dependency::Lazy;
+pub use dependency::Optional;
+pub use error::InjectError;
 pub use implements::Implements;
+pub use injectable::Injectable;
 pub use injector::Injector;
+pub use listener::Provision;
+pub use listener::ProvisionListener;
+pub use listener::Scope;
+pub use matcher::TypeMatcher;
+#[doc(hidden)]
+pub use method::invoke_method;
+pub use method::MethodCall;
+pub use method::MethodInterceptor;
+pub use method::MethodProxy;
 pub use module::AbstractModule;
 pub use module::BindFunc;
 pub use module::CombinedModule;
 pub use module::OverridableModule;
+pub use module::OverrideReport;
 pub use module::OverridedModule;
+pub use module::PriorityModule;
+pub use optional_binder::OptionalBinder;
+pub use provider::AroundProvider;
 pub use provider::Constructor;
 // pub use provider::std::sync::ArcProvider;
 // pub use provider::ImplConstructor;
 pub use provider::InterceptFunc;
+pub use provider::Intercepted;
+pub use provider::Proceed;
 pub use provider::Provider;
 pub use provider::ProviderAny;
 pub use provider::ProviderFunc;
+pub use provider::TryProvider;
+pub use recorder::Record;
+pub use recorder::Recorder;
+pub use rudi_macros::inject_test;
+pub use rudi_macros::interceptable;
+pub use rudi_macros::Inject;
+pub use stats::BindingStats;
 
 #[macro_export]
 macro_rules! bind {
@@ -44,20 +95,63 @@ macro_rules! intercept_dyn {
 
 #[macro_export]
 macro_rules! bind_dyn_constructor {
-    ($e:expr, $ty:tt, $cons:tt) => {
+    ($e:expr, $ty:tt, $cons:expr) => {
         $e.bind::<std::sync::Arc<dyn $ty>>()
-            .to_provider($crate::ProviderFunc(|i| {
-                let ret: std::sync::Arc<dyn $ty> = std::sync::Arc::new(i.inject_and_call($cons));
+            .to_constructor($crate::Constructor::map($cons, |x| {
+                let ret: std::sync::Arc<dyn $ty> = std::sync::Arc::new(x);
                 ret
             }))
     };
+}
 
-    ($e:expr, $ty:tt, $p:path) => {
+/// links `Arc<dyn $ty>` to the binding of `Arc<$impl>`, sharing its instance.
+#[macro_export]
+macro_rules! bind_dyn_impl {
+    ($e:expr, $ty:tt, $impl:ty) => {
         $e.bind::<std::sync::Arc<dyn $ty>>()
-            .to_provider($crate::ProviderFunc(|i| {
-                let ret: std::sync::Arc<dyn $ty> = std::sync::Arc::new(i.inject_and_call($p));
+            .to_impl_with(|x: std::sync::Arc<$impl>| {
+                let ret: std::sync::Arc<dyn $ty> = x;
                 ret
-            }))
+            })
+    };
+}
+
+/// implements `Upcast` for each trait object, so that `bind::<Arc<dyn Trait>>().to_impl::<I>()`
+/// links any implementation I of the trait.
+#[macro_export]
+macro_rules! impl_upcast {
+    ($($ty:tt),+) => {
+        $(
+            impl<I: $ty + 'static> $crate::Upcast<I> for dyn $ty {
+                fn upcast(i: std::sync::Arc<I>) -> std::sync::Arc<Self> {
+                    i
+                }
+            }
+        )+
+    };
+}
+
+/// exposes the binding of `Arc<$impl>` as `Arc<dyn $ty>` for each trait, sharing the same instance.
+#[macro_export]
+macro_rules! bind_dyn_alias {
+    ($e:expr, $impl:ty, $($ty:tt),+) => {
+        $(
+            $crate::bind_dyn_impl!($e, $ty, $impl);
+        )+
+    };
+}
+
+/// implements `Injectable` for each type using `Default::default`.
+#[macro_export]
+macro_rules! injectable_default {
+    ($($ty:ty),+) => {
+        $(
+            impl $crate::Injectable for $ty {
+                fn inject(_: &$crate::Injector) -> Self {
+                    Default::default()
+                }
+            }
+        )+
     };
 }
 
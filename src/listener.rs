@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use crate::InjectError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+    /// created on the first request
+    Singleton,
+    /// created when the injector is created
+    EagerSingleton,
+}
+
+/// A binding being provisioned.
+#[derive(Clone, Debug)]
+pub struct Provision {
+    pub type_name: String,
+    pub scope: Scope,
+    /// resolution path to the binding
+    pub path: String,
+}
+
+/// Observes every call to the providers, registered by `Binder::add_provision_listener`.
+pub trait ProvisionListener: Send + Sync {
+    fn before_provision(&self, _provision: &Provision) {}
+
+    /// duration includes the interceptors, outcome is Err if the provider failed or panicked.
+    fn after_provision(
+        &self,
+        _provision: &Provision,
+        _duration: Duration,
+        _outcome: Result<(), &InjectError>,
+    ) {
+    }
+}
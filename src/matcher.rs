@@ -0,0 +1,116 @@
+use std::{any::TypeId, sync::Arc};
+
+/// Selects bindings for the interceptors of `Binder::intercept_matching`,
+/// by the name of the binded type or by the tags of the binding.
+///
+/// the names are those of `std::any::type_name`, whose format is not guaranteed to be stable,
+/// so prefer the tags set by `BindOption::qualified` and `BindOption::marked`, or the module of origin.
+#[derive(Clone)]
+pub struct TypeMatcher {
+    f: MatchFn,
+}
+
+type MatchFn = Arc<dyn Fn(&str, &BindingTags) -> bool + Send + Sync>;
+
+/// Tags of a binding, which are matched by the structural predicates of `TypeMatcher`.
+#[derive(Clone, Default)]
+pub(crate) struct BindingTags {
+    // name of the innermost module which added the binding
+    pub(crate) module: Option<String>,
+    pub(crate) qualifiers: Vec<String>,
+    pub(crate) markers: Vec<TypeId>,
+}
+
+impl TypeMatcher {
+    /// matches the type names against pattern, where `*` matches any characters.
+    /// e.g. `alloc::sync::Arc<dyn *Repository*>`, which also matches `dyn UserRepository + Send`.
+    pub fn glob(pattern: &str) -> TypeMatcher {
+        let pattern = pattern.to_string();
+        TypeMatcher::from_fn(move |type_name| glob_match(&pattern, type_name))
+    }
+
+    pub fn from_fn<F>(f: F) -> TypeMatcher
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        TypeMatcher::with(move |type_name, _| f(type_name))
+    }
+
+    fn with<F>(f: F) -> TypeMatcher
+    where
+        F: Fn(&str, &BindingTags) -> bool + Send + Sync + 'static,
+    {
+        TypeMatcher { f: Arc::new(f) }
+    }
+
+    pub fn of<T: ?Sized + 'static>() -> TypeMatcher {
+        let name = std::any::type_name::<T>();
+        TypeMatcher::from_fn(move |type_name| type_name == name)
+    }
+
+    /// matches the bindings qualified by name with `BindOption::qualified`.
+    pub fn qualified(name: &str) -> TypeMatcher {
+        let name = name.to_string();
+        TypeMatcher::with(move |_, tags| tags.qualifiers.contains(&name))
+    }
+
+    /// matches the bindings marked by M with `BindOption::marked`, e.g. a marker trait `dyn Transactional`.
+    pub fn marked<M: ?Sized + 'static>() -> TypeMatcher {
+        let marker = TypeId::of::<M>();
+        TypeMatcher::with(move |_, tags| tags.markers.contains(&marker))
+    }
+
+    /// matches the bindings added by the modules whose name matches pattern, where `*` matches any characters.
+    /// the module of a binding is the innermost module which added it.
+    pub fn module(pattern: &str) -> TypeMatcher {
+        let pattern = pattern.to_string();
+        TypeMatcher::with(move |_, tags| {
+            tags.module
+                .as_deref()
+                .is_some_and(|m| glob_match(&pattern, m))
+        })
+    }
+
+    pub fn or(self, other: TypeMatcher) -> TypeMatcher {
+        TypeMatcher::with(move |type_name, tags| {
+            self.matches_tagged(type_name, tags) || other.matches_tagged(type_name, tags)
+        })
+    }
+
+    pub fn and(self, other: TypeMatcher) -> TypeMatcher {
+        TypeMatcher::with(move |type_name, tags| {
+            self.matches_tagged(type_name, tags) && other.matches_tagged(type_name, tags)
+        })
+    }
+
+    /// matches the type name of an untagged binding.
+    pub fn matches(&self, type_name: &str) -> bool {
+        self.matches_tagged(type_name, &BindingTags::default())
+    }
+
+    pub(crate) fn matches_tagged(&self, type_name: &str, tags: &BindingTags) -> bool {
+        (self.f)(type_name, tags)
+    }
+}
+
+fn glob_match(pattern: &str, s: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = s.strip_prefix(first) else {
+        return false;
+    };
+
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // no wildcard
+        return rest.is_empty();
+    };
+
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
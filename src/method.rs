@@ -0,0 +1,77 @@
+use std::{fmt::Display, sync::Arc};
+
+/// A method call on a proxy generated by `#[rudi::interceptable]`.
+#[derive(Clone, Debug)]
+pub struct MethodCall {
+    trait_name: &'static str,
+    method: &'static str,
+    args: Vec<String>,
+}
+
+impl MethodCall {
+    #[doc(hidden)]
+    pub fn new(trait_name: &'static str, method: &'static str, args: Vec<String>) -> MethodCall {
+        MethodCall {
+            trait_name,
+            method,
+            args,
+        }
+    }
+
+    pub fn trait_name(&self) -> &str {
+        self.trait_name
+    }
+
+    pub fn method(&self) -> &str {
+        self.method
+    }
+
+    /// debug view of each argument
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+}
+
+impl Display for MethodCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}::{}({})",
+            self.trait_name,
+            self.method,
+            self.args.join(", ")
+        )
+    }
+}
+
+/// Interceptor of the method calls on a proxy, which must call proceed exactly once
+/// to call the method of the next interceptor or the intercepted instance.
+pub trait MethodInterceptor: Send + Sync {
+    fn invoke(&self, call: &MethodCall, proceed: &mut dyn FnMut());
+}
+
+impl<F> MethodInterceptor for F
+where
+    F: Fn(&MethodCall, &mut dyn FnMut()) + Send + Sync,
+{
+    fn invoke(&self, call: &MethodCall, proceed: &mut dyn FnMut()) {
+        self(call, proceed)
+    }
+}
+
+/// Implemented for `dyn Trait` by `#[rudi::interceptable]`.
+pub trait MethodProxy {
+    fn proxy(inner: Arc<Self>, interceptors: Vec<Arc<dyn MethodInterceptor>>) -> Arc<Self>;
+}
+
+#[doc(hidden)]
+pub fn invoke_method(
+    interceptors: &[Arc<dyn MethodInterceptor>],
+    call: &MethodCall,
+    proceed: &mut dyn FnMut(),
+) {
+    match interceptors.split_first() {
+        Some((first, rest)) => first.invoke(call, &mut || invoke_method(rest, call, proceed)),
+        None => proceed(),
+    }
+}
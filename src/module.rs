@@ -1,7 +1,63 @@
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
 use crate::Binder;
 
 pub trait AbstractModule {
     fn config(&self, binder: &mut Binder);
+
+    /// name of the module in traces, which also identifies the interceptors it adds
+    fn name(&self) -> String {
+        std::any::type_name::<Self>().into()
+    }
+}
+
+/// configures m into binder, inside a span of the module and as the origin of the interceptors it adds.
+pub(crate) fn configure<M: AbstractModule + ?Sized>(m: &M, binder: &mut Binder) {
+    debug_span!("configure", module = %m.name());
+    let instance = m as *const M as *const () as usize;
+    let outer = binder.origin.replace(Origin::new(m.name(), instance));
+    m.config(binder);
+    binder.origin = outer;
+}
+
+/// Module being configured, which identifies the unnamed interceptors it adds
+/// by its name, its address and the order they are added in.
+/// The same instance installed twice, e.g. through an `Arc` shared by two modules, is the same origin.
+#[derive(Clone)]
+pub(crate) struct Origin {
+    module: String,
+    instance: usize,
+    added: Arc<AtomicUsize>,
+}
+
+impl Origin {
+    fn new(module: String, instance: usize) -> Origin {
+        Origin {
+            module,
+            instance,
+            added: Default::default(),
+        }
+    }
+
+    pub(crate) fn module(&self) -> &str {
+        &self.module
+    }
+
+    /// identity of the next interceptor added by the module
+    pub(crate) fn next(&self) -> (String, usize, usize) {
+        (
+            self.module.clone(),
+            self.instance,
+            self.added.fetch_add(1, Ordering::SeqCst),
+        )
+    }
 }
 
 #[derive(Clone)]
@@ -25,7 +81,9 @@ pub struct CombinedModule {
 
 impl AbstractModule for CombinedModule {
     fn config(&self, binder: &mut Binder) {
-        self.modules.iter().for_each(|m| m.config(binder))
+        self.modules
+            .iter()
+            .for_each(|m| configure(m.as_ref(), binder))
     }
 }
 
@@ -39,6 +97,13 @@ impl CombinedModule {
             overriden: self.modules.clone(),
         }
     }
+
+    pub fn with_priority(&self, priority: i32) -> PriorityModule {
+        PriorityModule {
+            priority,
+            modules: self.modules.clone(),
+        }
+    }
 }
 
 #[macro_export]
@@ -55,6 +120,38 @@ macro_rules! overridable_module {
     };
 }
 
+#[macro_export]
+macro_rules! priority_module {
+    ($p:expr, $($m:expr),*) => {
+        $crate::PriorityModule::new($p, vec![$(std::sync::Arc::new($m),)*])
+    };
+}
+
+/// Binds modules into the layer `priority` above the layer of the binder it is configured into.
+/// For each type, the binding of the highest layer wins, along with its eager flag.
+/// Interceptors apply to the bindings of the same or lower layers.
+pub struct PriorityModule {
+    priority: i32,
+    modules: Vec<std::sync::Arc<dyn AbstractModule>>,
+}
+
+impl PriorityModule {
+    pub fn new(priority: i32, modules: Vec<std::sync::Arc<dyn AbstractModule>>) -> PriorityModule {
+        PriorityModule { priority, modules }
+    }
+}
+
+impl AbstractModule for PriorityModule {
+    fn config(&self, binder: &mut Binder) {
+        let mut layer = binder.layer(binder.priority().saturating_add(self.priority));
+        self.modules
+            .iter()
+            .for_each(|m| configure(m.as_ref(), &mut layer));
+    }
+}
+
+/// Binds modules into the layer right below the layer of the binder it is configured into,
+/// so that any binding of the same type overrides them. Nesting lowers the layer further.
 pub struct OverridableModule {
     overriden: Vec<std::sync::Arc<dyn AbstractModule>>,
 }
@@ -68,6 +165,7 @@ impl OverridableModule {
         OverridedModule {
             overriden: self.overriden.clone(),
             overrides,
+            strict: false,
         }
     }
 }
@@ -75,23 +173,102 @@ impl OverridableModule {
 impl AbstractModule for OverridableModule {
     fn config(&self, binder: &mut Binder) {
         let mut ob = Binder::new();
-        self.overriden.iter().for_each(|m| m.config(&mut ob));
-        binder.merge_overridable(&ob);
+        self.overriden
+            .iter()
+            .for_each(|m| configure(m.as_ref(), &mut ob));
+        binder.layer(binder.priority().saturating_sub(1)).merge(&ob);
     }
 }
 
 pub struct OverridedModule {
     overriden: Vec<std::sync::Arc<dyn AbstractModule>>,
     overrides: Vec<std::sync::Arc<dyn AbstractModule>>,
+    strict: bool,
+}
+
+impl OverridedModule {
+    /// In strict mode every binding of the override modules must replace
+    /// a binding of the overridable modules, otherwise `config` panics.
+    pub fn strict(mut self) -> OverridedModule {
+        self.strict = true;
+        self
+    }
+
+    pub fn report(&self) -> OverrideReport {
+        let (overrides, overriden) = self.configure();
+        OverrideReport::new(&overriden, &overrides)
+    }
+
+    fn configure(&self) -> (Binder, Binder) {
+        let mut ov = Binder::new();
+        self.overrides
+            .iter()
+            .for_each(|m| configure(m.as_ref(), &mut ov));
+
+        let mut ob = Binder::new();
+        self.overriden
+            .iter()
+            .for_each(|m| configure(m.as_ref(), &mut ob));
+
+        (ov, ob)
+    }
 }
 
 impl AbstractModule for OverridedModule {
     fn config(&self, binder: &mut Binder) {
-        self.overrides.iter().for_each(|m| m.config(binder));
+        let (overrides, overriden) = self.configure();
 
-        let mut ob = Binder::new();
-        self.overriden.iter().for_each(|m| m.config(&mut ob));
+        if self.strict {
+            let report = OverrideReport::new(&overriden, &overrides);
+            if !report.unmatched_overrides.is_empty() {
+                panic!(
+                    "override binding does not replace any overridable binding: {}",
+                    report.unmatched_overrides.join(", ")
+                );
+            }
+        }
+
+        binder.install(&overrides);
+        binder
+            .layer(binder.priority().saturating_sub(1))
+            .merge(&overriden);
+    }
+}
+
+/// Result of comparing the bindings of an overridable module with its overrides.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OverrideReport {
+    /// overridable bindings that no override replaced
+    pub not_overridden: Vec<String>,
+    /// override bindings that shadowed nothing
+    pub unmatched_overrides: Vec<String>,
+}
+
+impl OverrideReport {
+    fn new(overriden: &Binder, overrides: &Binder) -> OverrideReport {
+        let base = overriden.binding_names();
+        let ov = overrides.binding_names();
 
-        binder.merge(&ob);
+        let mut not_overridden = difference(&base, &ov);
+        not_overridden.sort();
+
+        let mut unmatched_overrides = difference(&ov, &base);
+        unmatched_overrides.sort();
+
+        OverrideReport {
+            not_overridden,
+            unmatched_overrides,
+        }
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.not_overridden.is_empty() && self.unmatched_overrides.is_empty()
+    }
+}
+
+fn difference(a: &HashMap<TypeId, String>, b: &HashMap<TypeId, String>) -> Vec<String> {
+    a.iter()
+        .filter(|(key, _)| !b.contains_key(key))
+        .map(|(_, name)| name.clone())
+        .collect()
 }
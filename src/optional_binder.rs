@@ -0,0 +1,66 @@
+use std::{any::TypeId, collections::BTreeMap, marker::PhantomData};
+
+use crate::{
+    binder::{BindTo, OPTIONAL_DEFAULT_PRIORITY},
+    Binder,
+};
+
+#[derive(Clone, Default)]
+pub(crate) struct OptionalKey {
+    pub(crate) type_name: String,
+    // number of modules which set the binding, by layer
+    pub(crate) sets: BTreeMap<i32, usize>,
+}
+
+/// Binding which a library declares with or without a default, and any module may set.
+///
+/// The value set by `set_binding` overrides the default. If nothing is set and there is
+/// no default, the key is left unbinded and resolves to None through `Optional<T>`.
+/// Setting the binding from more than one module in the same layer is reported as a conflict
+/// on injector creation, while a higher layer overrides the value set in a lower one.
+pub struct OptionalBinder<T> {
+    binder: Binder,
+    type_id: TypeId,
+    type_name: String,
+    phantom: PhantomData<T>,
+}
+
+impl<T: 'static> OptionalBinder<T> {
+    pub(crate) fn new(binder: Binder) -> OptionalBinder<T> {
+        OptionalBinder {
+            binder,
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>().into(),
+            phantom: PhantomData,
+        }
+    }
+
+    fn update<F: FnOnce(&mut OptionalKey)>(&self, f: F) {
+        let mut m = self.binder.optionals.lock().unwrap();
+        let o = m.entry(self.type_id).or_insert_with(|| OptionalKey {
+            type_name: self.type_name.clone(),
+            ..Default::default()
+        });
+        f(o)
+    }
+
+    pub fn set_default(self) -> BindTo<T> {
+        let layer = self.binder.reserved_layer(OPTIONAL_DEFAULT_PRIORITY);
+
+        if layer.contains(self.type_id) {
+            panic!(
+                "conflicting defaults of optional binding {}",
+                self.type_name
+            );
+        }
+
+        layer.bind::<T>()
+    }
+
+    pub fn set_binding(self) -> BindTo<T> {
+        let priority = self.binder.priority();
+        self.update(|o| *o.sets.entry(priority).or_default() += 1);
+        self.binder.check_optionals();
+        self.binder.bind::<T>()
+    }
+}
@@ -1,9 +1,29 @@
-use std::{any::Any, marker::PhantomData};
+use std::{
+    any::{Any, TypeId},
+    marker::PhantomData,
+};
 
-use crate::Injector;
+use crate::{Dependency, InjectError, Injector};
 
 pub trait ProviderAny {
-    fn provide_any(&self, injector: &Injector) -> Box<dyn Any>;
+    fn provide_any(&self, injector: &Injector) -> Result<Box<dyn Any>, InjectError>;
+}
+
+/// provides `Vec<T>` from the elements added by `Binder::multibind`.
+pub(crate) struct ElementsProvider<T>(pub(crate) PhantomData<fn() -> T>);
+
+impl<T: 'static + Clone> ProviderAny for ElementsProvider<T> {
+    fn provide_any(&self, injector: &Injector) -> Result<Box<dyn Any>, InjectError> {
+        injector.record_dependency::<T>();
+
+        let elements = injector
+            .binds
+            .get_elements(TypeId::of::<T>())
+            .iter()
+            .map(|b| b.get_instance::<T>(injector))
+            .collect::<Result<Vec<T>, InjectError>>()?;
+        Ok(Box::new(elements))
+    }
 }
 
 pub trait Provider {
@@ -19,6 +39,23 @@ impl<T> Provider for ProviderFunc<T> {
         self.0(injector)
     }
 }
+/// Provider from a closure, which may capture runtime state.
+pub(crate) struct FnProvider<F, T> {
+    pub(crate) f: F,
+    pub(crate) pt: PhantomData<fn() -> T>,
+}
+
+impl<F, T> Provider for FnProvider<F, T>
+where
+    F: Fn(&Injector) -> T,
+{
+    type Provided = T;
+
+    fn provide(&self, injector: &Injector) -> Self::Provided {
+        (self.f)(injector)
+    }
+}
+
 pub(crate) struct BoxedProvider<T, P>
 where
     P: Provider<Provided = T>,
@@ -31,13 +68,41 @@ where
     P: Provider<Provided = T>,
     T: 'static,
 {
-    fn provide_any(&self, injector: &Injector) -> Box<dyn Any> {
-        Box::new(self.p.provide(injector))
+    fn provide_any(&self, injector: &Injector) -> Result<Box<dyn Any>, InjectError> {
+        Ok(Box::new(self.p.provide(injector)))
+    }
+}
+
+/// Provider which may fail. The error is returned by `Injector::try_get_instance`
+/// as `InjectError::ProviderFailed`, and the binding is provided again on the next request.
+pub trait TryProvider {
+    type Provided;
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    fn try_provide(&self, injector: &Injector) -> Result<Self::Provided, Self::Error>;
+}
+
+pub(crate) struct BoxedTryProvider<P: TryProvider>(pub(crate) P);
+
+impl<P: TryProvider> ProviderAny for BoxedTryProvider<P>
+where
+    P::Provided: 'static,
+{
+    fn provide_any(&self, injector: &Injector) -> Result<Box<dyn Any>, InjectError> {
+        match self.0.try_provide(injector) {
+            Ok(ins) => Ok(Box::new(ins)),
+            Err(e) => Err(InjectError::provider_failed::<P::Provided, _>(injector, e)),
+        }
     }
 }
 
 pub trait Constructor<A, R>: Sized {
-    fn new(&self, injector: &Injector) -> R;
+    fn try_new(&self, injector: &Injector) -> Result<R, InjectError>;
+
+    fn new(&self, injector: &Injector) -> R {
+        self.try_new(injector).unwrap_or_else(|e| panic!("{}", e))
+    }
+
     fn map<R2>(self, f: fn(R) -> R2) -> ConstructorMap<A, Self, R, R2> {
         ConstructorMap {
             c: self,
@@ -65,8 +130,8 @@ impl<A, C: Constructor<A, R1>, R1, R2> Provider for ConstructorMap<A, C, R1, R2>
 }
 
 impl<A, C: Constructor<A, R1>, R1, R2> Constructor<A, R2> for ConstructorMap<A, C, R1, R2> {
-    fn new(&self, injector: &Injector) -> R2 {
-        (self.f)(self.c.new(injector))
+    fn try_new(&self, injector: &Injector) -> Result<R2, InjectError> {
+        self.c.try_new(injector).map(self.f)
     }
 }
 
@@ -80,8 +145,9 @@ impl<A, T: 'static, C> ProviderAny for ConstructorProvider<A, T, C>
 where
     C: Constructor<A, T>,
 {
-    fn provide_any(&self, injector: &Injector) -> Box<dyn Any> {
-        Box::new(self.constructor.new(injector))
+    fn provide_any(&self, injector: &Injector) -> Result<Box<dyn Any>, InjectError> {
+        let ins = self.constructor.try_new(injector)?;
+        Ok(Box::new(ins))
     }
 }
 
@@ -95,11 +161,31 @@ where
     }
 }
 
+pub(crate) struct TryConstructorProvider<A, T, E, C: Constructor<A, Result<T, E>>> {
+    pub(crate) constructor: C,
+    pub(crate) pa: PhantomData<A>,
+    pub(crate) pt: PhantomData<T>,
+    pub(crate) pe: PhantomData<E>,
+}
+
+impl<A, T: 'static, E, C> ProviderAny for TryConstructorProvider<A, T, E, C>
+where
+    C: Constructor<A, Result<T, E>>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn provide_any(&self, injector: &Injector) -> Result<Box<dyn Any>, InjectError> {
+        match self.constructor.try_new(injector)? {
+            Ok(ins) => Ok(Box::new(ins)),
+            Err(e) => Err(InjectError::provider_failed::<T, _>(injector, e)),
+        }
+    }
+}
+
 pub(crate) struct SingletonProvider<T: Clone>(pub(crate) T);
 
 impl<T: Clone + 'static> ProviderAny for SingletonProvider<T> {
-    fn provide_any(&self, _injector: &Injector) -> Box<dyn Any> {
-        Box::new(self.0.clone())
+    fn provide_any(&self, _injector: &Injector) -> Result<Box<dyn Any>, InjectError> {
+        Ok(Box::new(self.0.clone()))
     }
 }
 
@@ -110,8 +196,16 @@ impl<T: Clone + 'static> Provider for SingletonProvider<T> {
     }
 }
 
+/// the rest of the interceptor chain, ending with the provider of the binding.
+pub(crate) type ProceedAny<'a> = &'a dyn Fn(&Injector) -> Result<Box<dyn Any>, InjectError>;
+
 pub trait InterceptProviderAny {
-    fn intercept_any(&self, injector: &Injector, ins: Box<dyn Any>) -> Box<dyn Any>;
+    fn around_any(
+        &self,
+        type_name: &str,
+        injector: &Injector,
+        proceed: ProceedAny,
+    ) -> Result<Box<dyn Any>, InjectError>;
 }
 
 pub trait InterceptProvider {
@@ -129,13 +223,143 @@ impl<T> InterceptProvider for InterceptFunc<T> {
     }
 }
 
+/// Interceptor from a closure, which may capture runtime state.
+pub(crate) struct FnIntercept<F, T> {
+    pub(crate) f: F,
+    pub(crate) pt: PhantomData<fn(T) -> T>,
+}
+
+impl<F, T> InterceptProvider for FnIntercept<F, T>
+where
+    F: Fn(&Injector, T) -> T,
+{
+    type Provided = T;
+
+    fn intercept(&self, injector: &Injector, ins: Self::Provided) -> Self::Provided {
+        (self.f)(injector, ins)
+    }
+}
+
 pub(crate) struct BoxedIntercept<T, P: InterceptProvider<Provided = T>>(pub(crate) P);
 
 impl<T: 'static, P: InterceptProvider<Provided = T>> InterceptProviderAny for BoxedIntercept<T, P> {
-    fn intercept_any(&self, injector: &Injector, ins: Box<dyn Any>) -> Box<dyn Any> {
-        let t = ins.downcast::<T>().unwrap();
+    fn around_any(
+        &self,
+        _: &str,
+        injector: &Injector,
+        proceed: ProceedAny,
+    ) -> Result<Box<dyn Any>, InjectError> {
+        let t = proceed(injector)?.downcast::<T>().unwrap();
         let ret = self.0.intercept(injector, *t);
-        Box::new(ret)
+        Ok(Box::new(ret))
+    }
+}
+
+/// Handle of an around interceptor to call the rest of the interceptor chain and the provider.
+pub struct Proceed<'a, T> {
+    injector: &'a Injector,
+    next: ProceedAny<'a>,
+    pt: PhantomData<fn() -> T>,
+}
+
+impl<T: 'static> Proceed<'_, T> {
+    pub fn proceed(self) -> Result<T, InjectError> {
+        let injector = self.injector;
+        self.proceed_with(injector)
+    }
+
+    /// proceeds with another injector, which resolves the dependencies of the provider.
+    pub fn proceed_with(self, injector: &Injector) -> Result<T, InjectError> {
+        (self.next)(injector).map(|ins| *ins.downcast::<T>().unwrap())
+    }
+}
+
+/// Binding intercepted by an interceptor of `Binder::intercept_matching`.
+pub struct Intercepted<'a> {
+    type_name: &'a str,
+    injector: &'a Injector,
+    next: ProceedAny<'a>,
+}
+
+impl Intercepted<'_> {
+    pub fn type_name(&self) -> &str {
+        self.type_name
+    }
+
+    /// provides the instance, which may be downcast to the binded type.
+    pub fn proceed(self) -> Result<Box<dyn Any>, InjectError> {
+        (self.next)(self.injector)
+    }
+
+    pub fn proceed_with(self, injector: &Injector) -> Result<Box<dyn Any>, InjectError> {
+        (self.next)(injector)
+    }
+}
+
+pub(crate) struct FnMatchingAround<F>(pub(crate) F);
+
+impl<F> InterceptProviderAny for FnMatchingAround<F>
+where
+    F: Fn(&Injector, Intercepted) -> Result<Box<dyn Any>, InjectError>,
+{
+    fn around_any(
+        &self,
+        type_name: &str,
+        injector: &Injector,
+        proceed: ProceedAny,
+    ) -> Result<Box<dyn Any>, InjectError> {
+        let intercepted = Intercepted {
+            type_name,
+            injector,
+            next: proceed,
+        };
+        (self.0)(injector, intercepted)
+    }
+}
+
+/// Interceptor wrapping the provider, which may skip, repeat or replace the construction.
+pub trait AroundProvider {
+    type Provided;
+
+    fn around(
+        &self,
+        injector: &Injector,
+        proceed: Proceed<Self::Provided>,
+    ) -> Result<Self::Provided, InjectError>;
+}
+
+pub(crate) struct FnAround<F, T> {
+    pub(crate) f: F,
+    pub(crate) pt: PhantomData<fn(T) -> T>,
+}
+
+impl<F, T> AroundProvider for FnAround<F, T>
+where
+    F: Fn(&Injector, Proceed<T>) -> Result<T, InjectError>,
+{
+    type Provided = T;
+
+    fn around(&self, injector: &Injector, proceed: Proceed<T>) -> Result<T, InjectError> {
+        (self.f)(injector, proceed)
+    }
+}
+
+pub(crate) struct BoxedAround<T, P: AroundProvider<Provided = T>>(pub(crate) P);
+
+impl<T: 'static, P: AroundProvider<Provided = T>> InterceptProviderAny for BoxedAround<T, P> {
+    fn around_any(
+        &self,
+        _: &str,
+        injector: &Injector,
+        proceed: ProceedAny,
+    ) -> Result<Box<dyn Any>, InjectError> {
+        let proceed = Proceed {
+            injector,
+            next: proceed,
+            pt: PhantomData,
+        };
+        let ret = self.0.around(injector, proceed)?;
+        Ok(Box::new(ret))
     }
 }
 
@@ -184,7 +408,7 @@ impl<T: 'static, P: InterceptProvider<Provided = T>> InterceptProviderAny for Bo
 macro_rules! cons_provider {
     (@get_instance $injector:expr, $ty:ty) => {
         {
-            $injector.get_instance::<$ty>().expect(&format!("type {} not binded.", std::any::type_name::<$ty>()))
+            <$ty as Dependency>::resolve($injector)?
         }
     };
     (
@@ -194,15 +418,15 @@ macro_rules! cons_provider {
         impl <F,$($ty,)* $last> Constructor<($($ty,)*), $last> for F
         where F : Fn($($ty,)*) -> $last,
         $(
-            $ty : 'static + Clone,
+            $ty : Dependency,
         )*
          {
-            fn new(&self, injector : &Injector) -> $last {
+            fn try_new(&self, injector : &Injector) -> Result<$last, InjectError> {
                 $(
                     let $ty = cons_provider!(@get_instance injector, $ty);
                 )*
                 let res = self($($ty,)*);
-                res
+                Ok(res)
             }
         }
     }
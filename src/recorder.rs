@@ -0,0 +1,76 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+use crate::Injector;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Record {
+    /// a key is requested from the injector
+    Resolved(String),
+    /// the provider of a binding is invoked
+    Provided(String),
+    /// an interceptor is applied to the instance of a binding.
+    /// interceptor is the name of the interceptor, or `#index` of an unnamed one
+    /// in the interceptors of the binding, in the order they are applied.
+    Intercepted {
+        type_name: String,
+        interceptor: String,
+    },
+}
+
+/// Records the usage of bindings of the injectors created by `Injector::with_recorder`,
+/// or configured by `Binder::set_recorder`.
+#[derive(Clone, Default)]
+pub struct Recorder {
+    records: Arc<Mutex<Vec<Record>>>,
+}
+
+impl Recorder {
+    pub fn new() -> Recorder {
+        Default::default()
+    }
+
+    pub(crate) fn record(&self, record: Record) {
+        self.records.lock().unwrap().push(record);
+    }
+
+    pub fn records(&self) -> Vec<Record> {
+        self.records.lock().unwrap().clone()
+    }
+
+    pub fn resolved_keys(&self) -> HashSet<String> {
+        self.records()
+            .into_iter()
+            .filter_map(|r| match r {
+                Record::Resolved(name) => Some(name),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// bindings of the injector never resolved while recording.
+    /// Eager singletons are resolved when the injector is created, so they are reported
+    /// unless the recorder is set by `Binder::set_recorder`, or they are resolved again.
+    pub fn unused_bindings(&self, injector: &Injector) -> Vec<String> {
+        let resolved = self.resolved_keys();
+
+        let mut unused: Vec<String> = injector
+            .binds
+            .get_bindings()
+            .into_iter()
+            .map(|b| b.type_name())
+            .filter(|name| !resolved.contains(name))
+            .collect();
+        unused.sort();
+        unused
+    }
+
+    pub fn assert_all_used(&self, injector: &Injector) {
+        let unused = self.unused_bindings(injector);
+        if !unused.is_empty() {
+            panic!("unused bindings: {}", unused.join(", "));
+        }
+    }
+}
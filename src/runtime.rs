@@ -0,0 +1,232 @@
+//! Runs the services of an injector until shutdown, with the `tokio` feature.
+
+use std::{any::TypeId, collections::HashSet, future::Future, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+
+use crate::{Binder, Implements, InjectError, Injector};
+
+/// A long running binding, started by `Runtime` when marked by `BindOption::as_service`.
+#[async_trait]
+pub trait Service: Send + Sync {
+    /// runs until stop is called.
+    async fn run(&self);
+
+    /// requests run to return.
+    async fn stop(&self);
+}
+
+#[async_trait]
+impl<S: Service + ?Sized> Service for Arc<S> {
+    async fn run(&self) {
+        self.as_ref().run().await
+    }
+
+    async fn stop(&self) {
+        self.as_ref().stop().await
+    }
+}
+
+type ServiceGetter = Arc<dyn Fn(&Injector) -> Result<Arc<dyn Service>, InjectError> + Send + Sync>;
+
+#[derive(Clone)]
+pub(crate) struct ServiceKey {
+    pub(crate) type_id: TypeId,
+    pub(crate) type_name: String,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) get: ServiceGetter,
+}
+
+impl ServiceKey {
+    pub(crate) fn new<T: Service + Clone + 'static>(timeout: Option<Duration>) -> ServiceKey {
+        ServiceKey {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>().into(),
+            timeout,
+            get: Arc::new(|injector| {
+                let s: Arc<dyn Service> = Arc::new(injector.try_get_instance::<T>()?);
+                Ok(s)
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StopError {
+    /// the service did not stop within the timeout, and its task is aborted
+    TimedOut(Duration),
+    Panicked(String),
+}
+
+/// Result of stopping each service, in the order they are stopped.
+#[derive(Clone, Debug, Default)]
+pub struct ShutdownReport {
+    pub results: Vec<(String, Result<(), StopError>)>,
+}
+
+impl ShutdownReport {
+    /// services which failed to stop
+    pub fn failed(&self) -> Vec<&str> {
+        self.results
+            .iter()
+            .filter(|(_, r)| r.is_err())
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+/// Builds the injector, runs its services as tasks until shutdown, then stops them
+/// in reverse dependency order and closes the injector.
+pub struct Runtime {
+    implements: Implements,
+    enabled: Vec<String>,
+    stop_timeout: Duration,
+}
+
+impl Runtime {
+    pub fn new(implements: Implements, enabled: Vec<String>) -> Runtime {
+        Runtime {
+            implements,
+            enabled,
+            stop_timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// timeout of the services not given one by `BindOption::as_service_with_timeout`, default 30 seconds.
+    pub fn stop_timeout(self, stop_timeout: Duration) -> Runtime {
+        Runtime {
+            stop_timeout,
+            ..self
+        }
+    }
+
+    /// runs until ctrl-c, or SIGTERM on unix.
+    pub fn run(self) -> impl Future<Output = Result<ShutdownReport, InjectError>> {
+        self.run_until(shutdown_signal())
+    }
+
+    /// runs until signal completes.
+    /// The injector is created by this call, so the returned future is Send if signal is Send.
+    /// If a service fails to be resolved, no service is started, and the error is returned
+    /// after the instances already created are destroyed.
+    pub fn run_until<F: Future<Output = ()>>(
+        self,
+        signal: F,
+    ) -> impl Future<Output = Result<ShutdownReport, InjectError>> {
+        let injector = self.implements.new_injector(self.enabled);
+        let stop_timeout = self.stop_timeout;
+
+        async move {
+            // moved as a whole, like the injector of `Injector::close_async`
+            let injector = injector;
+            let keys = injector.binds.services();
+
+            // every service is resolved before any is started, so that none is left running on failure
+            let mut services = Vec::new();
+            for key in keys {
+                match (key.get)(&injector) {
+                    Ok(service) => services.push((key, service)),
+                    Err(e) => {
+                        injector.close_async().await;
+                        return Err(e);
+                    }
+                }
+            }
+
+            let mut running = Vec::new();
+            for (key, service) in services {
+                let task = tokio::spawn({
+                    let service = service.clone();
+                    async move { service.run().await }
+                });
+                running.push((key, service, task));
+            }
+
+            signal.await;
+
+            let mut report = ShutdownReport::default();
+            while !running.is_empty() {
+                // the last started service which no remaining service depends on
+                let i = (0..running.len())
+                    .rev()
+                    .find(|&i| {
+                        let key = running[i].0.type_id;
+                        !running.iter().any(|(k, _, _)| {
+                            k.type_id != key && depends_on(&injector.binds, k.type_id, key)
+                        })
+                    })
+                    .unwrap_or(running.len() - 1);
+                let (key, service, mut task) = running.remove(i);
+
+                let timeout = key.timeout.unwrap_or(stop_timeout);
+                let stopped = tokio::time::timeout(timeout, async {
+                    service.stop().await;
+                    (&mut task).await
+                })
+                .await;
+
+                let result = match stopped {
+                    Ok(Ok(())) => Ok(()),
+                    Ok(Err(e)) => Err(StopError::Panicked(e.to_string())),
+                    Err(_) => {
+                        task.abort();
+                        Err(StopError::TimedOut(timeout))
+                    }
+                };
+                report.results.push((key.type_name, result));
+            }
+
+            injector.close_async().await;
+            Ok(report)
+        }
+    }
+}
+
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut term) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {},
+                    _ = term.recv() => {},
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+/// whether the instance of from depends on to, directly or indirectly.
+fn depends_on(binder: &Binder, from: TypeId, to: TypeId) -> bool {
+    let mut visited = HashSet::new();
+    let mut stack = vec![from];
+
+    while let Some(key) = stack.pop() {
+        if !visited.insert(key) {
+            continue;
+        }
+        if let Some(b) = binder.get_binding(key) {
+            let deps = b.deps.lock().unwrap();
+            if deps.contains(&to) {
+                return true;
+            }
+            stack.extend(deps.iter());
+        }
+    }
+    false
+}
+
+/// merges the services of other into services, keeping the first registration of each type.
+pub(crate) fn merge_services(services: &mut Vec<ServiceKey>, other: Vec<ServiceKey>) {
+    let known: HashSet<TypeId> = services.iter().map(|k| k.type_id).collect();
+    services.extend(other.into_iter().filter(|k| !known.contains(&k.type_id)));
+}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+/// Statistics of a binding, returned by `Injector::stats`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BindingStats {
+    pub type_name: String,
+    /// number of requests to the binding
+    pub resolutions: u64,
+    /// number of calls to the provider, including failed ones
+    pub constructions: u64,
+    /// time spent in the provider and interceptors
+    pub total_construction: Duration,
+    pub max_construction: Duration,
+    /// time spent in the interceptors, excluding the provider
+    pub interceptor_time: Duration,
+    /// time waiting for the instance lock, held while another thread provides the instance
+    pub lock_wait: Duration,
+}
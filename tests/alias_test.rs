@@ -0,0 +1,89 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::Counter;
+use rudi::{bind, bind_dyn_alias, intercept_dyn, BindFunc, Binder, Implements};
+
+trait Reader: Send + Sync {
+    fn read(&self) -> String;
+}
+
+trait Writer {
+    fn write(&self, s: &str);
+}
+
+trait Health {
+    fn healthy(&self) -> bool;
+}
+
+static CREATED: Counter = Counter::new();
+
+struct ServiceImpl {
+    data: Mutex<String>,
+}
+
+impl Reader for ServiceImpl {
+    fn read(&self) -> String {
+        self.data.lock().unwrap().clone()
+    }
+}
+
+impl Writer for ServiceImpl {
+    fn write(&self, s: &str) {
+        self.data.lock().unwrap().push_str(s)
+    }
+}
+
+impl Health for ServiceImpl {
+    fn healthy(&self) -> bool {
+        true
+    }
+}
+
+struct UpperReader {
+    inner: Arc<dyn Reader>,
+}
+
+impl Reader for UpperReader {
+    fn read(&self) -> String {
+        self.inner.read().to_uppercase()
+    }
+}
+
+fn service_module(binder: &mut Binder) {
+    bind!(binder, Arc<ServiceImpl>).to_constructor(|| {
+        CREATED.inc();
+        Arc::new(ServiceImpl {
+            data: Mutex::new(String::new()),
+        })
+    });
+
+    bind_dyn_alias!(binder, ServiceImpl, Reader, Writer, Health);
+
+    intercept_dyn!(binder, Reader).to_func(|_, inner| Arc::new(UpperReader { inner }));
+}
+
+#[test]
+fn alias_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(service_module));
+
+    let i = im.new_injector(vec![]);
+
+    let reader = i.get_instance::<Arc<dyn Reader>>().unwrap();
+    let writer = i.get_instance::<Arc<dyn Writer>>().unwrap();
+    let health = i.get_instance::<Arc<dyn Health>>().unwrap();
+    let service = i.get_instance::<Arc<ServiceImpl>>().unwrap();
+
+    writer.write("hello");
+
+    assert_eq!(reader.read(), "HELLO");
+    assert_eq!(service.read(), "hello");
+    assert!(health.healthy());
+    assert_eq!(
+        Arc::as_ptr(&writer) as *const (),
+        Arc::as_ptr(&service) as *const ()
+    );
+    assert_eq!(CREATED.get(), 1);
+}
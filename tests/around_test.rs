@@ -0,0 +1,76 @@
+mod common;
+
+use common::Counter;
+use rudi::{bind, BindFunc, Binder, Implements};
+
+static CREATED: Counter = Counter::new();
+
+#[derive(Clone)]
+struct Report {
+    text: String,
+}
+
+fn report_module(binder: &mut Binder) {
+    bind!(binder, String).to_singleton("live".into());
+    bind!(binder, Report).to_constructor(|text: String| {
+        CREATED.inc();
+        Report { text }
+    });
+}
+
+fn stub_module(binder: &mut Binder) {
+    bind!(binder, Report).to_constructor(|| -> Report { panic!("stubbed provider called") });
+    binder.intercept::<Report>().to_around_fn(|_, _| {
+        Ok(Report {
+            text: "stub".into(),
+        })
+    });
+}
+
+fn count_module(binder: &mut Binder) {
+    binder.intercept::<Report>().to_around_fn(|_, proceed| {
+        let before = CREATED.get();
+        let r = proceed.proceed()?;
+        Ok(Report {
+            text: format!("{} {}", r.text, CREATED.get() - before),
+        })
+    });
+    binder.intercept::<Report>().order(-1).to_fn(|_, r| Report {
+        text: format!("[{}]", r.text),
+    });
+}
+
+#[test]
+fn skip_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(stub_module));
+
+    let i = im.new_injector(vec![]);
+    assert_eq!(i.get_instance::<Report>().unwrap().text, "stub");
+}
+
+#[test]
+fn proceed_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(report_module));
+    im.add_bind(BindFunc(count_module));
+
+    let i = im.new_injector(vec![]);
+    assert_eq!(i.get_instance::<Report>().unwrap().text, "[live] 1");
+}
+
+fn fallback_module(binder: &mut Binder) {
+    bind!(binder, u32).to_try_constructor(|| "x".parse::<u32>());
+    binder
+        .intercept::<u32>()
+        .to_around_fn(|_, proceed| proceed.proceed().or(Ok(0)));
+}
+
+#[test]
+fn fallback_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(fallback_module));
+
+    let i = im.new_injector(vec![]);
+    assert_eq!(i.try_get_instance::<u32>().unwrap(), 0);
+}
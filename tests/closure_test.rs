@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use rudi::{bind, bind_dyn_constructor, intercept_dyn, BindFunc, Binder, Implements};
+
+trait Greeter: Send + Sync {
+    fn greet(&self) -> String;
+}
+
+struct GreeterImpl {
+    greeting: String,
+    name: String,
+}
+
+impl Greeter for GreeterImpl {
+    fn greet(&self) -> String {
+        format!("{} {}", self.greeting, self.name)
+    }
+}
+
+struct LoudGreeter {
+    inner: Arc<dyn Greeter>,
+    suffix: String,
+}
+
+impl Greeter for LoudGreeter {
+    fn greet(&self) -> String {
+        format!("{}{}", self.inner.greet(), self.suffix)
+    }
+}
+
+#[test]
+fn closure_test() {
+    let greeting = String::from("hello");
+    let name = String::from("rudi");
+    let suffix = String::from("!");
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(move |binder: &mut Binder| {
+        let name = name.clone();
+        bind!(binder, String).to_provider_fn(move |_| name.clone());
+
+        let greeting = greeting.clone();
+        bind_dyn_constructor!(binder, Greeter, move |name: String| GreeterImpl {
+            greeting: greeting.clone(),
+            name,
+        });
+
+        let suffix = suffix.clone();
+        intercept_dyn!(binder, Greeter).to_fn(move |_, inner| {
+            Arc::new(LoudGreeter {
+                inner,
+                suffix: suffix.clone(),
+            })
+        });
+    }));
+
+    let i = im.new_injector(vec![]);
+    let g = i.get_instance::<Arc<dyn Greeter>>().unwrap();
+
+    assert_eq!(g.greet(), "hello rudi!");
+}
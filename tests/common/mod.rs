@@ -0,0 +1,59 @@
+//! Fixtures shared by the integration tests, each of which uses only some of them.
+#![allow(dead_code)]
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+pub trait Hello {
+    fn hello(&self) -> String;
+}
+
+pub struct HelloWorld {
+    name: String,
+}
+
+impl Hello for HelloWorld {
+    fn hello(&self) -> String {
+        format!("hello {}", self.name)
+    }
+}
+
+impl HelloWorld {
+    pub fn new(name: String) -> Self {
+        HelloWorld { name }
+    }
+}
+
+/// number of calls, e.g. of a provider, which may be kept in a static.
+#[derive(Default)]
+pub struct Counter(AtomicUsize);
+
+impl Counter {
+    pub const fn new() -> Self {
+        Counter(AtomicUsize::new(0))
+    }
+
+    pub fn inc(&self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn get(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// events pushed by the bindings and interceptors of a test, in order.
+#[derive(Clone, Default)]
+pub struct Log(Arc<Mutex<Vec<String>>>);
+
+impl Log {
+    pub fn push(&self, event: impl Into<String>) {
+        self.0.lock().unwrap().push(event.into());
+    }
+
+    pub fn events(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
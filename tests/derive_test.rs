@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use rudi::{bind, BindFunc, Binder, Implements, Inject, Injector, Lazy, Optional};
+
+#[derive(Inject)]
+struct Deps {
+    injector: Injector,
+    port: u16,
+    names: Vec<String>,
+    timeout: Optional<u64>,
+    lazy: Lazy<Arc<Worker>>,
+}
+
+#[derive(Inject)]
+struct Many(
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+);
+
+struct Server {
+    deps: Deps,
+}
+
+struct Worker {
+    server: Arc<Server>,
+}
+
+// Deps holds the injector, which is not Sync.
+#[allow(clippy::arc_with_non_send_sync)]
+fn server_module(binder: &mut Binder) {
+    bind!(binder, u8).to_singleton(1);
+    bind!(binder, u16).to_singleton(8080);
+    binder.multibind::<String>().to_singleton("a".into());
+    bind!(binder, Arc<Server>).to_constructor(|deps: Deps| Arc::new(Server { deps }));
+    bind!(binder, Arc<Worker>).to_constructor(|server: Arc<Server>| Arc::new(Worker { server }));
+    bind!(binder, u32).to_constructor(|m: Many| {
+        [
+            m.0, m.1, m.2, m.3, m.4, m.5, m.6, m.7, m.8, m.9, m.10, m.11, m.12, m.13, m.14, m.15,
+            m.16,
+        ]
+        .iter()
+        .map(|x| *x as u32)
+        .sum()
+    });
+}
+
+fn name_module(binder: &mut Binder) {
+    binder
+        .multibind::<String>()
+        .to_constructor(|port: u16| format!("port {}", port));
+}
+
+#[test]
+fn derive_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(server_module));
+    im.add_bind(BindFunc(name_module));
+
+    let i = im.new_injector(vec![]);
+    let s = i.get_instance::<Arc<Server>>().unwrap();
+
+    assert_eq!(s.deps.port, 8080);
+    assert_eq!(s.deps.names, vec!["a", "port 8080"]);
+    assert!(s.deps.timeout.is_none());
+    assert_eq!(s.deps.injector.get_instance::<u16>(), Some(8080));
+
+    let worker = s.deps.lazy.get();
+    assert!(Arc::ptr_eq(&worker.server, &s));
+
+    assert_eq!(i.get_instance::<u32>(), Some(17));
+}
+
+#[test]
+fn multibind_override_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(server_module));
+    im.add_bind(BindFunc(name_module));
+
+    im.add_bind(BindFunc(|binder: &mut Binder| {
+        bind!(binder, Vec<String>).to_singleton(vec!["only".into()]);
+    }));
+
+    let i = im.new_injector(vec![]);
+
+    assert_eq!(i.get_instance::<Vec<String>>().unwrap(), vec!["only"]);
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::Log;
+use rudi::{bind, BindFunc, Binder, Implements, Lazy};
+
+#[derive(Clone)]
+struct Pool {
+    log: Log,
+}
+
+impl Drop for Pool {
+    fn drop(&mut self) {
+        self.log.push("drop pool");
+    }
+}
+
+#[derive(Clone)]
+struct Repository {
+    pool: Arc<Pool>,
+}
+
+#[derive(Clone)]
+struct Service {
+    repository: Arc<Repository>,
+}
+
+impl Drop for Service {
+    fn drop(&mut self) {
+        self.repository.pool.log.push("drop service");
+    }
+}
+
+fn app_module(log: Log) -> impl Fn(&mut Binder) {
+    move |binder: &mut Binder| {
+        let log = log.clone();
+        bind!(binder, Arc<Pool>).to_provider_fn(move |_| Arc::new(Pool { log: log.clone() }));
+        bind!(binder, Arc<Repository>)
+            .to_constructor(|pool: Arc<Pool>| Arc::new(Repository { pool }));
+        bind!(binder, Arc<Service>)
+            .to_constructor(|repository: Arc<Repository>| Arc::new(Service { repository }));
+    }
+}
+
+#[test]
+fn final_drop_test() {
+    let log = Log::default();
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(app_module(log.clone())));
+
+    let i = im.new_injector(vec![]);
+    i.get_instance::<Arc<Service>>().unwrap();
+    let other = i.clone();
+    drop(i);
+    assert!(log.events().is_empty());
+
+    drop(other);
+    assert_eq!(
+        log.events(),
+        vec![String::from("drop service"), String::from("drop pool")]
+    );
+}
+
+struct Flusher {
+    pool: Lazy<Arc<Pool>>,
+}
+
+fn lazy_module(log: Log) -> impl Fn(&mut Binder) {
+    move |binder: &mut Binder| {
+        let (l1, l2) = (log.clone(), log.clone());
+        bind!(binder, Arc<Pool>)
+            .to_provider_fn(move |_| Arc::new(Pool { log: l1.clone() }))
+            .on_destroy(|_| ());
+        bind!(binder, Flusher)
+            .to_shared_constructor(|pool: Lazy<Arc<Pool>>| Flusher { pool })
+            .on_destroy(move |f| {
+                // the pool is still available while the flusher is destroyed
+                f.pool.get();
+                l2.push("flush");
+            });
+    }
+}
+
+#[test]
+fn dependency_order_test() {
+    let log = Log::default();
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(lazy_module(log.clone())));
+
+    let i = im.new_injector(vec![]);
+    let flusher = i.get_shared::<Flusher>().unwrap();
+    // the pool is created after the flusher
+    flusher.pool.get();
+    drop(flusher);
+
+    i.close();
+    assert_eq!(
+        log.events(),
+        vec![String::from("flush"), String::from("drop pool")]
+    );
+}
+
+#[test]
+fn lazy_final_drop_test() {
+    let log = Log::default();
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(lazy_module(log.clone())));
+
+    let i = im.new_injector(vec![]);
+    let flusher = i.get_shared::<Flusher>().unwrap();
+    flusher.pool.get();
+    drop(flusher);
+
+    // the injector of Lazy does not keep the flusher alive
+    drop(i);
+    assert_eq!(
+        log.events(),
+        vec![String::from("flush"), String::from("drop pool")]
+    );
+}
+
+#[tokio::test]
+async fn close_async_test() {
+    let log = Log::default();
+    let flushed = log.clone();
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(app_module(log.clone())));
+    im.add_bind(BindFunc(move |binder: &mut Binder| {
+        let flushed = flushed.clone();
+        binder
+            .bind::<u32>()
+            .to_singleton(1)
+            .on_destroy_async(move |_| {
+                let flushed = flushed.clone();
+                async move {
+                    tokio::task::yield_now().await;
+                    flushed.push("flushed");
+                }
+            });
+    }));
+
+    let i = im.new_injector(vec![]);
+    i.get_instance::<Arc<Service>>().unwrap();
+    i.get_instance::<u32>().unwrap();
+
+    // the future is Send, so it can be spawned
+    tokio::spawn(i.close_async()).await.unwrap();
+    assert_eq!(
+        log.events(),
+        vec![
+            String::from("flushed"),
+            String::from("drop service"),
+            String::from("drop pool")
+        ]
+    );
+}
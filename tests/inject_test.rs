@@ -0,0 +1,73 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{Hello, HelloWorld};
+use rudi::{bind, bind_dyn_constructor, AbstractModule, BindFunc, Binder, Inject, Optional};
+
+struct HelloModule;
+
+impl AbstractModule for HelloModule {
+    fn config(&self, binder: &mut Binder) {
+        bind!(binder, String).to_singleton("world".into());
+        bind_dyn_constructor!(binder, Hello, HelloWorld::new);
+    }
+}
+
+fn mock_module(binder: &mut Binder) {
+    bind!(binder, String).to_singleton("mock".into());
+}
+
+#[rudi::inject_test(modules(HelloModule))]
+fn inject_test(hello: Arc<dyn Hello>, name: String) {
+    assert_eq!(hello.hello(), "hello world");
+    assert_eq!(name, "world");
+}
+
+#[rudi::inject_test(modules(HelloModule), overrides(BindFunc(mock_module)))]
+fn inject_overrides_test(hello: Arc<dyn Hello>) {
+    assert_eq!(hello.hello(), "hello mock");
+}
+
+#[rudi::inject_test(modules(HelloModule))]
+#[tokio::test]
+async fn inject_async_test(hello: Arc<dyn Hello>) {
+    assert_eq!(hello.hello(), "hello world");
+}
+
+#[derive(Inject)]
+struct Deps {
+    hello: Arc<dyn Hello>,
+    name: String,
+}
+
+#[rudi::inject_test(modules(HelloModule))]
+fn inject_dependency_test(deps: Deps, port: Optional<u16>) {
+    assert_eq!(deps.hello.hello(), "hello world");
+    assert_eq!(deps.name, "world");
+    assert!(port.is_none());
+}
+
+fn no_name_module(binder: &mut Binder) {
+    bind_dyn_constructor!(binder, Hello, HelloWorld::new);
+}
+
+#[rudi::inject_test(modules(BindFunc(no_name_module)))]
+#[should_panic(
+    expected = "type alloc::string::String not binded. path = alloc::sync::Arc<dyn inject_test::common::Hello>"
+)]
+fn inject_error_test(_hello: Arc<dyn Hello>) {}
+
+// the attribute does not shadow the builtin test attribute of glob imports
+mod glob_import {
+    use rudi::*;
+
+    #[test]
+    fn glob_import_test() {
+        let mut im = Implements::new();
+        im.add_bind(BindFunc(|binder: &mut Binder| {
+            bind!(binder, u8).to_singleton(1);
+        }));
+        assert_eq!(im.new_injector(vec![]).get_instance::<u8>(), Some(1));
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use rudi::{
+    bind, AbstractModule, BindFunc, Binder, CombinedModule, Implements, Injector, OverridableModule,
+};
+
+fn base_module(binder: &mut Binder) {
+    bind!(binder, String).to_singleton("base".into());
+}
+
+fn suffix(_: &Injector, s: String) -> String {
+    format!("{} suffix", s)
+}
+
+fn intercept_module(binder: &mut Binder) {
+    binder
+        .intercept::<String>()
+        .named("suffix")
+        .order(10)
+        .to_func(suffix);
+    binder
+        .intercept::<String>()
+        .named("prefix")
+        .to_func(|_, s| format!("prefix {}", s));
+}
+
+#[test]
+fn order_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(base_module));
+    im.add_bind(BindFunc(intercept_module));
+
+    let i = im.new_injector(vec![]);
+    assert_eq!(i.get_instance::<String>().unwrap(), "prefix base suffix");
+}
+
+#[test]
+fn dedup_test() {
+    // a library module included by two overridable modules
+    let shared: Arc<dyn AbstractModule> = Arc::new(BindFunc(intercept_module));
+
+    let mut im = Implements::new();
+    im.add_bind(OverridableModule::new(vec![
+        Arc::new(BindFunc(base_module)),
+        shared.clone(),
+    ]));
+    im.add_bind(OverridableModule::new(vec![shared]));
+
+    let i = im.new_injector(vec![]);
+    assert_eq!(i.get_instance::<String>().unwrap(), "prefix base suffix");
+}
+
+fn prefix_module(prefix: &'static str) -> impl Fn(&mut Binder) {
+    move |binder: &mut Binder| {
+        binder
+            .intercept::<String>()
+            .to_fn(move |_, s| format!("{}{}", prefix, s));
+    }
+}
+
+#[test]
+fn unnamed_dedup_test() {
+    // the same module instance installed twice
+    let shared: Arc<dyn AbstractModule> = Arc::new(BindFunc(prefix_module("x")));
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(base_module));
+    im.add_bind(CombinedModule::new(vec![shared.clone(), shared]));
+
+    let i = im.new_injector(vec![]);
+    assert_eq!(i.get_instance::<String>().unwrap(), "xbase");
+}
+
+#[test]
+fn unnamed_instances_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(base_module));
+    im.add_bind(BindFunc(prefix_module("x")));
+    im.add_bind(BindFunc(prefix_module("y")));
+
+    let i = im.new_injector(vec![]);
+    assert_eq!(i.get_instance::<String>().unwrap(), "yxbase");
+}
+
+fn replace_module(binder: &mut Binder) {
+    binder
+        .intercept::<String>()
+        .named("prefix")
+        .to_func(|_, s| format!("mock {}", s));
+    binder.intercept::<String>().remove("suffix");
+}
+
+#[test]
+fn override_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(base_module));
+    im.add_bind(BindFunc(intercept_module));
+
+    let i = im.new_injector(vec![]);
+    let mocked = i.with_overrides(BindFunc(replace_module));
+
+    assert_eq!(mocked.get_instance::<String>().unwrap(), "mock base");
+    assert_eq!(i.get_instance::<String>().unwrap(), "prefix base suffix");
+}
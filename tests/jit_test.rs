@@ -0,0 +1,108 @@
+mod common;
+
+use common::Counter;
+use rudi::{bind, injectable_default, BindFunc, Binder, Implements, Injectable, Injector, Jit};
+
+static CREATED: Counter = Counter::new();
+
+#[derive(Clone)]
+struct Clock {
+    offset: u32,
+}
+
+impl Injectable for Clock {
+    fn inject(injector: &Injector) -> Self {
+        CREATED.inc();
+        Clock {
+            offset: injector.get_instance::<u32>().unwrap(),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct Settings {
+    verbose: bool,
+}
+
+injectable_default!(Settings);
+
+fn config_module(binder: &mut Binder) {
+    bind!(binder, u32).to_singleton(9);
+}
+
+fn jit_module(binder: &mut Binder) {
+    binder.enable_jit_bindings();
+}
+
+fn strict_module(binder: &mut Binder) {
+    binder.require_explicit_bindings();
+}
+
+#[test]
+fn jit_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(config_module));
+    im.add_bind(BindFunc(jit_module));
+
+    let i = im.new_injector(vec![]);
+
+    assert_eq!(i.get_injectable::<Clock>().unwrap().offset, 9);
+    assert_eq!(i.get_injectable::<Clock>().unwrap().offset, 9);
+    assert!(i.get_instance::<Clock>().is_some());
+    assert_eq!(CREATED.get(), 1);
+    assert!(!i.get_injectable::<Settings>().unwrap().verbose);
+}
+
+#[derive(Clone)]
+struct Logger {
+    settings: Settings,
+}
+
+#[test]
+fn jit_argument_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(jit_module));
+    im.add_bind(BindFunc(|binder: &mut Binder| {
+        bind!(binder, Logger).to_constructor(|settings: Jit<Settings>| Logger {
+            settings: settings.into_inner(),
+        });
+    }));
+
+    let i = im.new_injector(vec![]);
+
+    assert!(!i.get_instance::<Logger>().unwrap().settings.verbose);
+    assert!(i.get_instance::<Settings>().is_some());
+}
+
+#[test]
+fn jit_disabled_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(config_module));
+
+    let i = im.new_injector(vec![]);
+    assert!(i.get_injectable::<Clock>().is_none());
+
+    im.add_bind(BindFunc(jit_module));
+    im.add_bind(BindFunc(strict_module));
+
+    let i = im.new_injector(vec![]);
+    assert!(i.try_get_injectable::<Clock>().is_err());
+}
+
+#[test]
+fn jit_optional_default_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(config_module));
+    im.add_bind(BindFunc(jit_module));
+
+    let i = im.new_injector(vec![]);
+    assert!(!i.get_injectable::<Settings>().unwrap().verbose);
+
+    let i = i.with_overrides(BindFunc(|binder: &mut Binder| {
+        binder
+            .optional::<Settings>()
+            .set_default()
+            .to_singleton(Settings { verbose: true });
+    }));
+    assert!(i.get_injectable::<Settings>().unwrap().verbose);
+}
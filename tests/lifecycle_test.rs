@@ -0,0 +1,86 @@
+mod common;
+
+use common::Log;
+use rudi::{bind, BindFunc, Binder, Implements, InjectError};
+
+#[derive(Clone)]
+struct Pool {
+    size: u32,
+}
+
+#[derive(Clone)]
+struct Service {
+    pool: Pool,
+}
+
+fn lifecycle_module(events: Log) -> impl Fn(&mut Binder) {
+    move |binder: &mut Binder| {
+        let (e1, e2, e3, e4) = (
+            events.clone(),
+            events.clone(),
+            events.clone(),
+            events.clone(),
+        );
+        bind!(binder, u32).to_singleton(4);
+        bind!(binder, Pool)
+            .to_constructor(|size: u32| Pool { size })
+            .on_init(move |p, _| e1.push(format!("init pool {}", p.size)))
+            .on_destroy(move |_| e2.push("destroy pool"));
+        bind!(binder, Service)
+            .to_constructor(|pool: Pool| Service { pool })
+            .on_init(move |s, injector| {
+                let size = injector.get_instance::<u32>().unwrap();
+                assert_eq!(s.pool.size, size);
+                e3.push("init service")
+            })
+            .on_destroy(move |_| e4.push("destroy service"));
+    }
+}
+
+#[test]
+fn lifecycle_test() {
+    let events = Log::default();
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(lifecycle_module(events.clone())));
+
+    let i = im.new_injector(vec![]);
+    i.get_instance::<Service>().unwrap();
+    i.get_instance::<Service>().unwrap();
+    i.close();
+
+    assert_eq!(
+        events.events(),
+        vec![
+            String::from("init pool 4"),
+            String::from("init service"),
+            String::from("destroy service"),
+            String::from("destroy pool"),
+        ]
+    );
+}
+
+#[test]
+fn closed_test() {
+    let events = Log::default();
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(lifecycle_module(events.clone())));
+
+    let i = im.new_injector(vec![]);
+    i.get_instance::<Pool>().unwrap();
+    i.close();
+
+    assert!(matches!(
+        i.try_get_instance::<Service>(),
+        Err(InjectError::Closed { .. })
+    ));
+    assert!(matches!(
+        i.try_get_instance::<Pool>(),
+        Err(InjectError::Closed { .. })
+    ));
+    assert_eq!(
+        events.events(),
+        vec![String::from("init pool 4"), String::from("destroy pool")]
+    );
+}
@@ -0,0 +1,101 @@
+use std::sync::{Arc, Mutex};
+
+use rudi::{bind, bind_dyn_impl, impl_upcast, BindFunc, Binder, Implements, Injectable, Injector};
+
+trait Hello {
+    fn hello(&self) -> String;
+}
+
+trait Named {
+    fn rename(&self, name: &str);
+}
+
+trait Farewell {
+    fn bye(&self) -> String;
+}
+
+impl_upcast!(Hello, Named, Farewell);
+
+struct HelloWorld {
+    name: Mutex<String>,
+}
+
+impl Hello for HelloWorld {
+    fn hello(&self) -> String {
+        format!("hello {}", self.name.lock().unwrap())
+    }
+}
+
+impl Named for HelloWorld {
+    fn rename(&self, name: &str) {
+        *self.name.lock().unwrap() = name.into();
+    }
+}
+
+struct Goodbye {
+    name: String,
+}
+
+impl Farewell for Goodbye {
+    fn bye(&self) -> String {
+        format!("bye {}", self.name)
+    }
+}
+
+impl Injectable for Goodbye {
+    fn inject(injector: &Injector) -> Self {
+        Goodbye {
+            name: injector.get_instance::<String>().unwrap(),
+        }
+    }
+}
+
+fn link_module(binder: &mut Binder) {
+    bind!(binder, String).to_singleton("world".into());
+    bind!(binder, Arc<HelloWorld>).to_constructor(|name: String| {
+        Arc::new(HelloWorld {
+            name: Mutex::new(name),
+        })
+    });
+
+    bind!(binder, Arc<dyn Hello>).to_impl::<HelloWorld>();
+    bind_dyn_impl!(binder, Named, HelloWorld);
+}
+
+#[test]
+fn link_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(link_module));
+
+    let i = im.new_injector(vec![]);
+
+    let hello = i.get_instance::<Arc<dyn Hello>>().unwrap();
+    let named = i.get_instance::<Arc<dyn Named>>().unwrap();
+    let concrete = i.get_instance::<Arc<HelloWorld>>().unwrap();
+
+    assert_eq!(hello.hello(), "hello world");
+    named.rename("rudi");
+    assert_eq!(hello.hello(), "hello rudi");
+    assert_eq!(concrete.hello(), "hello rudi");
+}
+
+#[test]
+fn injectable_link_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(link_module));
+    im.add_bind(BindFunc(|binder: &mut Binder| {
+        binder.enable_jit_bindings();
+        bind!(binder, Arc<dyn Farewell>).to_injectable_impl::<Goodbye>();
+    }));
+
+    let i = im.new_injector(vec![]);
+
+    let farewell = i.get_instance::<Arc<dyn Farewell>>().unwrap();
+    let goodbye = i.get_instance::<Arc<Goodbye>>().unwrap();
+
+    assert_eq!(farewell.bye(), "bye world");
+    assert_eq!(
+        Arc::as_ptr(&farewell) as *const (),
+        Arc::as_ptr(&goodbye) as *const ()
+    );
+}
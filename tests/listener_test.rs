@@ -0,0 +1,79 @@
+mod common;
+
+use std::{thread::sleep, time::Duration};
+
+use common::Log;
+use rudi::{bind, BindFunc, Binder, Implements, InjectError, Provision, ProvisionListener, Scope};
+
+#[derive(Clone, Default)]
+struct Timings {
+    events: Log,
+    slow: Log,
+}
+
+impl ProvisionListener for Timings {
+    fn before_provision(&self, provision: &Provision) {
+        self.events.push(format!("before {}", provision.path));
+    }
+
+    fn after_provision(
+        &self,
+        provision: &Provision,
+        duration: Duration,
+        outcome: Result<(), &InjectError>,
+    ) {
+        let outcome = if outcome.is_ok() { "ok" } else { "failed" };
+        self.events
+            .push(format!("after {} {}", provision.type_name, outcome));
+        if duration >= Duration::from_millis(10) {
+            self.slow.push(provision.type_name.clone());
+        }
+        if provision.type_name == "u64" {
+            assert_eq!(provision.scope, Scope::EagerSingleton);
+        }
+    }
+}
+
+fn app_module(binder: &mut Binder) {
+    bind!(binder, u32).to_constructor(|| {
+        sleep(Duration::from_millis(10));
+        1
+    });
+    bind!(binder, String).to_constructor(|n: u32| n.to_string());
+    bind!(binder, u64).to_singleton(2).as_eager();
+    bind!(binder, i32).to_try_constructor(|| "x".parse::<i32>());
+}
+
+#[test]
+fn listener_test() {
+    let timings = Timings::default();
+    let listener = timings.clone();
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(app_module));
+    im.add_bind(BindFunc(move |binder: &mut Binder| {
+        binder.add_provision_listener(listener.clone())
+    }));
+
+    let i = im.new_injector(vec![]);
+    assert_eq!(i.get_instance::<String>().unwrap(), "1");
+    assert!(i.try_get_instance::<i32>().is_err());
+
+    assert_eq!(
+        timings.events.events(),
+        vec![
+            String::from("before u64"),
+            String::from("after u64 ok"),
+            String::from("before alloc::string::String"),
+            String::from("before alloc::string::String -> u32"),
+            String::from("after u32 ok"),
+            String::from("after alloc::string::String ok"),
+            String::from("before i32"),
+            String::from("after i32 failed"),
+        ]
+    );
+    assert_eq!(
+        timings.slow.events(),
+        vec![String::from("u32"), String::from("alloc::string::String")]
+    );
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::Log;
+use rudi::{bind, bind_dyn_constructor, AbstractModule, BindFunc, Binder, Implements, TypeMatcher};
+
+trait UserRepository {
+    fn user(&self) -> String;
+}
+
+trait OrderRepository {
+    fn order(&self) -> u32;
+}
+
+struct MemoryUsers;
+impl UserRepository for MemoryUsers {
+    fn user(&self) -> String {
+        "kim".into()
+    }
+}
+
+struct MemoryOrders;
+impl OrderRepository for MemoryOrders {
+    fn order(&self) -> u32 {
+        1
+    }
+}
+
+trait Audited {}
+
+struct Repositories;
+
+impl AbstractModule for Repositories {
+    fn config(&self, binder: &mut Binder) {
+        bind_dyn_constructor!(binder, UserRepository, || MemoryUsers).qualified("primary");
+        bind_dyn_constructor!(binder, OrderRepository, || MemoryOrders).marked::<dyn Audited>();
+        bind!(binder, u32).to_singleton(3);
+    }
+}
+
+fn logging_module(matcher: TypeMatcher, log: Log) -> impl Fn(&mut Binder) {
+    move |binder: &mut Binder| {
+        let log = log.clone();
+        binder
+            .intercept_matching(matcher.clone())
+            .named("log")
+            .to_around_fn(move |_, intercepted| {
+                log.push(intercepted.type_name());
+                intercepted.proceed()
+            });
+    }
+}
+
+fn unlog_module(binder: &mut Binder) {
+    binder
+        .intercept_matching(TypeMatcher::glob("*"))
+        .remove("log");
+}
+
+#[test]
+fn matching_test() {
+    let log = Log::default();
+
+    let mut im = Implements::new();
+    im.add_bind(Repositories);
+    im.add_bind(BindFunc(logging_module(
+        TypeMatcher::glob("alloc::sync::Arc<dyn *Repository>"),
+        log.clone(),
+    )));
+
+    let i = im.new_injector(vec![]);
+    assert_eq!(
+        i.get_instance::<Arc<dyn UserRepository>>().unwrap().user(),
+        "kim"
+    );
+    assert_eq!(
+        i.get_instance::<Arc<dyn OrderRepository>>()
+            .unwrap()
+            .order(),
+        1
+    );
+    assert_eq!(i.get_instance::<u32>().unwrap(), 3);
+
+    assert_eq!(
+        log.events(),
+        vec![
+            String::from("alloc::sync::Arc<dyn matching_test::UserRepository>"),
+            String::from("alloc::sync::Arc<dyn matching_test::OrderRepository>"),
+        ]
+    );
+
+    let unlogged = i.with_overrides(BindFunc(unlog_module));
+    unlogged.get_instance::<Arc<dyn UserRepository>>().unwrap();
+    assert_eq!(log.events().len(), 2);
+}
+
+#[test]
+fn glob_test() {
+    let m = TypeMatcher::glob("alloc::sync::Arc<dyn *Repository>");
+    assert!(m.matches("alloc::sync::Arc<dyn app::UserRepository>"));
+    assert!(!m.matches("alloc::sync::Arc<dyn app::UserRepository + Send>"));
+    assert!(!m.matches("alloc::string::String"));
+
+    let m = TypeMatcher::glob("alloc::sync::Arc<dyn *Repository*>");
+    assert!(m.matches("alloc::sync::Arc<dyn app::UserRepository + Send>"));
+
+    let m = TypeMatcher::of::<String>().or(TypeMatcher::glob("u*"));
+    assert!(m.matches("alloc::string::String"));
+    assert!(m.matches("u32"));
+    assert!(!m.matches("i32"));
+}
+
+fn logged(matcher: TypeMatcher) -> Vec<String> {
+    let log = Log::default();
+
+    let mut im = Implements::new();
+    im.add_bind(Repositories);
+    im.add_bind(BindFunc(logging_module(matcher, log.clone())));
+
+    let i = im.new_injector(vec![]);
+    i.get_instance::<Arc<dyn UserRepository>>().unwrap();
+    i.get_instance::<Arc<dyn OrderRepository>>().unwrap();
+    i.get_instance::<u32>().unwrap();
+
+    log.events()
+}
+
+#[test]
+fn structural_matching_test() {
+    assert_eq!(
+        logged(TypeMatcher::qualified("primary")),
+        vec![String::from(
+            "alloc::sync::Arc<dyn matching_test::UserRepository>"
+        )]
+    );
+    assert_eq!(
+        logged(TypeMatcher::marked::<dyn Audited>()),
+        vec![String::from(
+            "alloc::sync::Arc<dyn matching_test::OrderRepository>"
+        )]
+    );
+    assert_eq!(logged(TypeMatcher::module("*::Repositories")).len(), 3);
+    assert_eq!(
+        logged(TypeMatcher::module("*::Repositories").and(TypeMatcher::marked::<dyn Audited>()))
+            .len(),
+        1
+    );
+}
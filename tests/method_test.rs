@@ -0,0 +1,66 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::Log;
+use rudi::{bind_dyn_constructor, intercept_dyn, BindFunc, Binder, Implements, MethodCall};
+
+#[rudi::interceptable]
+trait Greeter {
+    fn greet(&self, name: &str, times: u32) -> String;
+    fn id(&self) -> &str;
+}
+
+struct SimpleGreeter {
+    id: String,
+}
+
+impl Greeter for SimpleGreeter {
+    fn greet(&self, name: &str, times: u32) -> String {
+        format!("hello {}", name).repeat(times as usize)
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+fn greeter_module(binder: &mut Binder) {
+    bind_dyn_constructor!(binder, Greeter, || SimpleGreeter {
+        id: "simple".into()
+    });
+}
+
+fn logging_module(log: Log) -> impl Fn(&mut Binder) {
+    move |binder: &mut Binder| {
+        let log = log.clone();
+        intercept_dyn!(binder, Greeter).to_method_interceptor(
+            move |call: &MethodCall, proceed: &mut dyn FnMut()| {
+                log.push(call.to_string());
+                proceed()
+            },
+        );
+    }
+}
+
+#[test]
+fn method_intercept_test() {
+    let log = Log::default();
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(greeter_module));
+    im.add_bind(BindFunc(logging_module(log.clone())));
+
+    let i = im.new_injector(vec![]);
+    let greeter = i.get_instance::<Arc<dyn Greeter>>().unwrap();
+
+    assert_eq!(greeter.greet("kim", 2), "hello kimhello kim");
+    assert_eq!(greeter.id(), "simple");
+    assert_eq!(
+        log.events(),
+        vec![
+            String::from("Greeter::greet(\"kim\", 2)"),
+            String::from("Greeter::id()"),
+        ]
+    );
+}
@@ -0,0 +1,110 @@
+use rudi::{BindFunc, Binder, Implements, Optional};
+
+#[derive(Clone)]
+struct Timeout(u64);
+
+#[derive(Clone)]
+struct Client {
+    timeout: u64,
+    retries: Option<u32>,
+}
+
+fn library_module(binder: &mut Binder) {
+    binder
+        .optional::<Timeout>()
+        .set_default()
+        .to_singleton(Timeout(30));
+    binder.optional::<u32>();
+
+    binder
+        .bind::<Client>()
+        .to_constructor(|timeout: Timeout, retries: Optional<u32>| Client {
+            timeout: timeout.0,
+            retries: retries.into_inner(),
+        });
+}
+
+fn app_module(binder: &mut Binder) {
+    binder
+        .optional::<Timeout>()
+        .set_binding()
+        .to_singleton(Timeout(5));
+    binder.optional::<u32>().set_binding().to_singleton(3);
+}
+
+#[test]
+fn default_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(library_module));
+
+    let i = im.new_injector(vec![]);
+    let c = i.get_instance::<Client>().unwrap();
+
+    assert_eq!(c.timeout, 30);
+    assert_eq!(c.retries, None);
+}
+
+#[test]
+fn set_binding_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(app_module));
+    im.add_bind(BindFunc(library_module));
+
+    let i = im.new_injector(vec![]);
+    let c = i.get_instance::<Client>().unwrap();
+
+    assert_eq!(c.timeout, 5);
+    assert_eq!(c.retries, Some(3));
+}
+
+#[test]
+fn layered_set_binding_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(library_module));
+    im.add_bind(BindFunc(app_module));
+    im.add_bind(rudi::priority_module!(
+        1,
+        BindFunc(|b: &mut Binder| {
+            b.optional::<u32>().set_binding().to_singleton(4);
+        })
+    ));
+
+    let i = im.new_injector(vec![]);
+    assert_eq!(i.get_instance::<Client>().unwrap().retries, Some(4));
+}
+
+#[test]
+#[should_panic(expected = "conflicting bindings of optional binding u32")]
+fn conflict_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(library_module));
+    im.add_bind(BindFunc(app_module));
+    im.add_bind(BindFunc(|b: &mut Binder| {
+        b.optional::<u32>().set_binding().to_singleton(4);
+    }));
+
+    im.new_injector(vec![]);
+}
+
+#[test]
+#[should_panic(expected = "conflicting bindings of optional binding u32")]
+fn override_conflict_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(library_module));
+
+    let i = im.new_injector(vec![]);
+    i.with_overrides(BindFunc(|b: &mut Binder| {
+        b.optional::<u32>().set_binding().to_singleton(4);
+        b.optional::<u32>().set_binding().to_singleton(5);
+    }));
+}
+
+#[test]
+#[should_panic(expected = "conflicting defaults of optional binding")]
+fn default_conflict_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(library_module));
+    im.add_bind(BindFunc(library_module));
+
+    im.new_injector(vec![]);
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use rudi::{bind, bind_dyn, BindFunc, Binder, Implements, Optional};
+
+trait Metrics {
+    fn count(&self) -> u32;
+}
+
+struct MetricsImpl;
+
+impl Metrics for MetricsImpl {
+    fn count(&self) -> u32 {
+        1
+    }
+}
+
+#[derive(Clone)]
+struct Service {
+    metrics: Option<Arc<dyn Metrics>>,
+}
+
+fn new_service(metrics: Optional<Arc<dyn Metrics>>) -> Service {
+    Service {
+        metrics: metrics.into_inner(),
+    }
+}
+
+fn service_module(binder: &mut Binder) {
+    bind!(binder, Service).to_constructor(new_service);
+}
+
+fn metrics_module(binder: &mut Binder) {
+    bind_dyn!(binder, Metrics).to_singleton(Arc::new(MetricsImpl));
+}
+
+#[test]
+fn optional_none_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(service_module));
+
+    let i = im.new_injector(vec![]);
+    let s = i.get_instance::<Service>().unwrap();
+
+    assert!(s.metrics.is_none());
+}
+
+#[test]
+fn optional_some_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(service_module));
+    im.add_bind(BindFunc(metrics_module));
+
+    let i = im.new_injector(vec![]);
+    let s = i.get_instance::<Service>().unwrap();
+
+    assert_eq!(s.metrics.unwrap().count(), 1);
+}
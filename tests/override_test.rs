@@ -0,0 +1,226 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::Counter;
+use rudi::{
+    bind, overridable_module, priority_module, AbstractModule, BindFunc, Binder, Implements,
+};
+
+#[derive(Clone)]
+struct Config {
+    name: String,
+}
+
+fn default_module(binder: &mut Binder) {
+    bind!(binder, Config).to_singleton(Config {
+        name: "default".into(),
+    });
+    bind!(binder, u32).to_singleton(42);
+}
+
+fn override_module(binder: &mut Binder) {
+    bind!(binder, Config).to_singleton(Config {
+        name: "override".into(),
+    });
+}
+
+fn typo_module(binder: &mut Binder) {
+    bind!(binder, u64).to_singleton(7);
+}
+
+#[test]
+fn report_test() {
+    let m = overridable_module!(BindFunc(default_module)).with(vec![
+        Arc::new(BindFunc(override_module)),
+        Arc::new(BindFunc(typo_module)),
+    ]);
+
+    let report = m.report();
+
+    assert_eq!(report.not_overridden, vec![String::from("u32")]);
+    assert_eq!(report.unmatched_overrides, vec![String::from("u64")]);
+}
+
+#[test]
+fn strict_test() {
+    let m = overridable_module!(BindFunc(default_module))
+        .with(vec![Arc::new(BindFunc(override_module))])
+        .strict();
+
+    let mut im = Implements::new();
+    im.add_bind(m);
+
+    let i = im.new_injector(vec![]);
+    assert_eq!(
+        i.get_instance::<Config>().unwrap().name,
+        String::from("override")
+    );
+}
+
+#[test]
+#[should_panic]
+fn strict_unmatched_test() {
+    let m = overridable_module!(BindFunc(default_module))
+        .with(vec![Arc::new(BindFunc(typo_module))])
+        .strict();
+
+    let mut binder = Binder::new();
+    m.config(&mut binder);
+}
+
+#[test]
+fn lowest_layer_test() {
+    let mut im = Implements::new();
+    im.add_bind(priority_module!(
+        i32::MIN,
+        overridable_module!(BindFunc(default_module))
+            .with(vec![Arc::new(BindFunc(override_module))])
+    ));
+    im.add_bind(BindFunc(|binder: &mut Binder| {
+        binder.optional::<u32>().set_default().to_singleton(0);
+    }));
+
+    let i = im.new_injector(vec![]);
+
+    assert_eq!(
+        i.get_instance::<Config>().unwrap().name,
+        String::from("override")
+    );
+    assert_eq!(i.get_instance::<u32>().unwrap(), 42);
+}
+
+fn platform_module(binder: &mut Binder) {
+    bind!(binder, Config).to_singleton(Config {
+        name: "platform".into(),
+    });
+    bind!(binder, u32).to_singleton(1);
+}
+
+fn test_module(binder: &mut Binder) {
+    bind!(binder, u32).to_singleton(3);
+}
+
+#[test]
+fn layered_test() {
+    let mut im = Implements::new();
+
+    let library = overridable_module!(BindFunc(default_module));
+    let platform = overridable_module!(library, BindFunc(platform_module));
+
+    im.add_bind(overridable_module!(platform));
+    im.add_bind(BindFunc(override_module));
+    im.add_bind(priority_module!(10, BindFunc(test_module)));
+
+    let i = im.new_injector(vec![]);
+
+    assert_eq!(
+        i.get_instance::<Config>().unwrap().name,
+        String::from("override")
+    );
+    assert_eq!(i.get_instance::<u32>().unwrap(), 3);
+}
+
+fn eager_default_module(binder: &mut Binder) {
+    bind!(binder, Config)
+        .to_constructor(|| -> Config { panic!("overridden eager binding created") })
+        .as_eager();
+    binder
+        .intercept::<Config>()
+        .to_func(|_, _| panic!("interceptor of overridden binding applied"));
+}
+
+fn intercept_module(binder: &mut Binder) {
+    binder.intercept::<Config>().to_func(|_, c| Config {
+        name: format!("intercepted {}", c.name),
+    });
+}
+
+#[test]
+fn layered_intercept_test() {
+    let mut im = Implements::new();
+
+    im.add_bind(overridable_module!(BindFunc(eager_default_module)));
+    im.add_bind(BindFunc(override_module));
+    im.add_bind(BindFunc(intercept_module));
+
+    let i = im.new_injector(vec![]);
+
+    assert_eq!(
+        i.get_instance::<Config>().unwrap().name,
+        String::from("intercepted override")
+    );
+}
+
+static HELLO_CREATED: Counter = Counter::new();
+
+#[derive(Clone)]
+struct Hello {
+    config: Config,
+}
+
+fn app_module(binder: &mut Binder) {
+    bind!(binder, Config).to_singleton(Config { name: "app".into() });
+    bind!(binder, Hello).to_constructor(|config: Config| {
+        HELLO_CREATED.inc();
+        Hello { config }
+    });
+    bind!(binder, Arc<u32>).to_constructor(|| Arc::new(42));
+}
+
+fn mock_module(binder: &mut Binder) {
+    bind!(binder, Config).to_singleton(Config {
+        name: "mock".into(),
+    });
+}
+
+#[test]
+fn with_overrides_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(app_module));
+
+    let i = im.new_injector(vec![]);
+    assert_eq!(i.get_instance::<Hello>().unwrap().config.name, "app");
+    let answer = i.get_instance::<Arc<u32>>().unwrap();
+
+    let mocked = i.with_overrides(BindFunc(mock_module));
+    assert_eq!(mocked.get_instance::<Hello>().unwrap().config.name, "mock");
+    assert_eq!(HELLO_CREATED.get(), 2);
+    assert!(Arc::ptr_eq(
+        &answer,
+        &mocked.get_instance::<Arc<u32>>().unwrap()
+    ));
+
+    assert_eq!(i.get_instance::<Hello>().unwrap().config.name, "app");
+    assert_eq!(HELLO_CREATED.get(), 2);
+}
+
+#[test]
+fn with_overrides_parent_drop_test() {
+    let destroyed = Arc::new(Counter::new());
+    let counter = destroyed.clone();
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(app_module));
+    im.add_bind(BindFunc(move |binder: &mut Binder| {
+        let counter = counter.clone();
+        bind!(binder, Arc<String>)
+            .to_constructor(|| Arc::new(String::from("shared")))
+            .on_destroy(move |_| counter.inc());
+    }));
+
+    let i = im.new_injector(vec![]);
+    let shared = i.get_instance::<Arc<String>>().unwrap();
+    let mocked = i.with_overrides(BindFunc(mock_module));
+
+    // the instance reused by mocked outlives the parent
+    drop(i);
+    assert_eq!(destroyed.get(), 0);
+    assert!(Arc::ptr_eq(
+        &shared,
+        &mocked.get_instance::<Arc<String>>().unwrap()
+    ));
+
+    drop(mocked);
+    assert_eq!(destroyed.get(), 1);
+}
@@ -1,8 +1,11 @@
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 use rudi::{
-    bind_dyn_constructor, get_instance_dyn, new_injector, AbstractModule, BindFunc, Binder,
-    Implements,
+    bind, bind_dyn_constructor, get_instance_dyn, new_injector, AbstractModule, BindFunc, Binder,
+    Implements, InjectError, RetryPolicy,
 };
 
 trait A {}
@@ -88,3 +91,63 @@ fn not_binded_test() {
 
     let _ins = get_instance_dyn!(i, C);
 }
+
+static PANIC_ONCE: AtomicUsize = AtomicUsize::new(0);
+
+fn panic_once() -> u32 {
+    if PANIC_ONCE.fetch_add(1, Ordering::SeqCst) == 0 {
+        panic!("first call panics");
+    }
+    42
+}
+
+#[test]
+fn provider_panic_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(|binder: &mut Binder| {
+        bind!(binder, u32).to_constructor(panic_once);
+        bind!(binder, String).to_constructor(|n: u32| n.to_string());
+    }));
+
+    let i = new_injector!(im);
+
+    let err = i.try_get_instance::<String>().err().unwrap();
+    match err {
+        InjectError::ProviderPanicked {
+            type_name,
+            path,
+            message,
+        } => {
+            assert_eq!(type_name, "u32");
+            assert_eq!(path, "alloc::string::String -> u32");
+            assert_eq!(message, "first call panics");
+        }
+        _ => panic!("unexpected error {}", err),
+    }
+
+    assert_eq!(i.get_instance::<String>().unwrap(), "42");
+}
+
+#[test]
+fn retry_never_test() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(move |binder: &mut Binder| {
+        let counter = counter.clone();
+        bind!(binder, u64)
+            .to_constructor(move || -> u64 {
+                counter.fetch_add(1, Ordering::SeqCst);
+                panic!("always panics")
+            })
+            .retry(RetryPolicy::Never);
+    }));
+
+    let i = new_injector!(im);
+
+    let first = i.try_get_instance::<u64>().err().unwrap().to_string();
+    let second = i.try_get_instance::<u64>().err().unwrap().to_string();
+    assert_eq!(first, second);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
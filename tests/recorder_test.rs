@@ -0,0 +1,89 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{Hello, HelloWorld};
+use rudi::{bind, bind_dyn_constructor, BindFunc, Binder, Implements, Record, Recorder};
+
+fn hello_module(binder: &mut Binder) {
+    bind!(binder, String).to_singleton("world".into());
+    bind!(binder, u32).to_singleton(42);
+    bind_dyn_constructor!(binder, Hello, HelloWorld::new);
+    binder
+        .intercept::<String>()
+        .to_func(|_, s| s.to_uppercase());
+}
+
+#[test]
+fn record_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(hello_module));
+
+    let recorder = Recorder::new();
+    let i = im.new_injector(vec![]).with_recorder(&recorder);
+
+    let hello = i.get_instance::<Arc<dyn Hello>>().unwrap();
+    assert_eq!(hello.hello(), "hello WORLD");
+
+    let hello_name = std::any::type_name::<Arc<dyn Hello>>().to_string();
+    assert_eq!(
+        recorder.records(),
+        vec![
+            Record::Resolved(hello_name.clone()),
+            Record::Provided(hello_name),
+            Record::Resolved("alloc::string::String".into()),
+            Record::Provided("alloc::string::String".into()),
+            Record::Intercepted {
+                type_name: "alloc::string::String".into(),
+                interceptor: "#0".into(),
+            },
+        ]
+    );
+
+    assert_eq!(recorder.unused_bindings(&i), vec![String::from("u32")]);
+}
+
+fn eager_module(binder: &mut Binder) {
+    bind!(binder, u64)
+        .to_constructor(|x: u32| x as u64)
+        .as_eager();
+    binder
+        .intercept::<u64>()
+        .named("double")
+        .to_func(|_, x| x * 2);
+}
+
+#[test]
+fn eager_recorded_test() {
+    let recorder = Recorder::new();
+    let r = recorder.clone();
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(hello_module));
+    im.add_bind(BindFunc(eager_module));
+    im.add_bind(BindFunc(move |binder: &mut Binder| binder.set_recorder(&r)));
+
+    let i = im.new_injector(vec![]);
+    i.get_instance::<Arc<dyn Hello>>().unwrap();
+
+    assert!(recorder.records().contains(&Record::Provided("u64".into())));
+    assert!(recorder.records().contains(&Record::Intercepted {
+        type_name: "u64".into(),
+        interceptor: "double".into(),
+    }));
+    recorder.assert_all_used(&i);
+}
+
+#[test]
+#[should_panic(expected = "unused bindings: u32")]
+fn assert_all_used_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(hello_module));
+
+    let recorder = Recorder::new();
+    let i = im.new_injector(vec![]).with_recorder(&recorder);
+
+    i.get_instance::<Arc<dyn Hello>>().unwrap();
+
+    recorder.assert_all_used(&i);
+}
@@ -0,0 +1,154 @@
+#![cfg(feature = "tokio")]
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use rudi::{
+    bind,
+    runtime::{Runtime, Service, StopError},
+    BindFunc, Binder, Implements, InjectError,
+};
+use tokio::sync::Notify;
+
+#[derive(Default)]
+struct Database {
+    stop: Notify,
+}
+
+#[async_trait]
+impl Service for Database {
+    async fn run(&self) {
+        self.stop.notified().await
+    }
+
+    async fn stop(&self) {
+        self.stop.notify_one()
+    }
+}
+
+struct Server {
+    _db: Arc<Database>,
+    stop: Notify,
+}
+
+#[async_trait]
+impl Service for Server {
+    async fn run(&self) {
+        self.stop.notified().await
+    }
+
+    async fn stop(&self) {
+        self.stop.notify_one()
+    }
+}
+
+struct Stuck;
+
+#[async_trait]
+impl Service for Stuck {
+    async fn run(&self) {
+        std::future::pending::<()>().await
+    }
+
+    async fn stop(&self) {}
+}
+
+fn server_module(binder: &mut Binder) {
+    bind!(binder, Arc<Database>)
+        .to_constructor(|| Arc::new(Database::default()))
+        .as_service();
+    bind!(binder, Arc<Server>)
+        .to_constructor(|db: Arc<Database>| {
+            Arc::new(Server {
+                _db: db,
+                stop: Notify::new(),
+            })
+        })
+        .as_service();
+    bind!(binder, Arc<Stuck>)
+        .to_singleton(Arc::new(Stuck))
+        .as_service_with_timeout(Duration::from_millis(20));
+}
+
+#[tokio::test]
+async fn runtime_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(server_module));
+
+    let run = Runtime::new(im, vec![])
+        .stop_timeout(Duration::from_secs(1))
+        .run_until(tokio::time::sleep(Duration::from_millis(10)));
+    let report = tokio::spawn(run).await.unwrap().unwrap();
+
+    let names: Vec<&str> = report.results.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "alloc::sync::Arc<runtime_test::Stuck>",
+            "alloc::sync::Arc<runtime_test::Server>",
+            "alloc::sync::Arc<runtime_test::Database>",
+        ]
+    );
+    assert_eq!(
+        report.results[0].1,
+        Err(StopError::TimedOut(Duration::from_millis(20)))
+    );
+    assert_eq!(
+        report.failed(),
+        vec!["alloc::sync::Arc<runtime_test::Stuck>"]
+    );
+}
+
+#[derive(Debug)]
+struct Unavailable;
+
+impl std::fmt::Display for Unavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unavailable")
+    }
+}
+
+impl std::error::Error for Unavailable {}
+
+#[tokio::test]
+async fn failed_service_test() {
+    let destroyed = Arc::new(AtomicBool::new(false));
+    let created = Arc::new(AtomicBool::new(false));
+
+    let mut im = Implements::new();
+    im.add_bind(BindFunc({
+        let (destroyed, created) = (destroyed.clone(), created.clone());
+        move |binder: &mut Binder| {
+            let created = created.clone();
+            bind!(binder, Arc<Database>)
+                .to_provider_fn(move |_| {
+                    created.store(true, Ordering::SeqCst);
+                    Arc::new(Database::default())
+                })
+                .on_destroy({
+                    let destroyed = destroyed.clone();
+                    move |_| destroyed.store(true, Ordering::SeqCst)
+                })
+                .as_service();
+            bind!(binder, Arc<Server>)
+                .to_try_constructor(|_: Arc<Database>| -> Result<Arc<Server>, Unavailable> {
+                    Err(Unavailable)
+                })
+                .as_service();
+        }
+    }));
+
+    let res = Runtime::new(im, vec![])
+        .run_until(std::future::pending())
+        .await;
+
+    assert!(matches!(res, Err(InjectError::ProviderFailed { .. })));
+    assert!(created.load(Ordering::SeqCst));
+    assert!(destroyed.load(Ordering::SeqCst));
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::Counter;
+use rudi::{bind, BindFunc, Binder, Implements};
+
+static POOL_CREATED: Counter = Counter::new();
+
+// neither Pool nor Service is Clone
+struct Pool {
+    size: usize,
+}
+
+struct Service {
+    pool: Arc<Pool>,
+}
+
+fn shared_module(binder: &mut Binder) {
+    bind!(binder, usize).to_singleton(8);
+    bind!(binder, Pool).to_shared_constructor(|size: usize| {
+        POOL_CREATED.inc();
+        Pool { size }
+    });
+    bind!(binder, Service).to_shared_constructor(|pool: Arc<Pool>| Service { pool });
+}
+
+#[test]
+fn shared_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(shared_module));
+
+    let i = im.new_injector(vec![]);
+
+    let pool = i.get_shared::<Pool>().unwrap();
+    let service = i.try_get_shared::<Service>().unwrap();
+
+    assert_eq!(pool.size, 8);
+    assert!(Arc::ptr_eq(&pool, &service.pool));
+    assert_eq!(POOL_CREATED.get(), 1);
+    assert!(i.get_shared::<String>().is_none());
+}
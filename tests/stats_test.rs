@@ -0,0 +1,46 @@
+use std::{thread::sleep, time::Duration};
+
+use rudi::{bind, BindFunc, Binder, Implements};
+
+fn app_module(binder: &mut Binder) {
+    bind!(binder, u32).to_constructor(|| {
+        sleep(Duration::from_millis(5));
+        1
+    });
+    bind!(binder, String).to_constructor(|n: u32| n.to_string());
+    binder.intercept::<String>().to_fn(|_, s| {
+        sleep(Duration::from_millis(5));
+        s
+    });
+    bind!(binder, i32).to_try_constructor(|| "x".parse::<i32>());
+}
+
+#[test]
+fn stats_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(app_module));
+
+    let i = im.new_injector(vec![]);
+    i.get_instance::<String>().unwrap();
+    i.get_instance::<String>().unwrap();
+    i.get_instance::<u32>().unwrap();
+    assert!(i.try_get_instance::<i32>().is_err());
+    assert!(i.try_get_instance::<i32>().is_err());
+
+    let stats = i.stats();
+    let names: Vec<&str> = stats.iter().map(|s| s.type_name.as_str()).collect();
+    assert_eq!(names, vec!["alloc::string::String", "i32", "u32"]);
+
+    let (string, failing, number) = (&stats[0], &stats[1], &stats[2]);
+
+    assert_eq!((string.resolutions, string.constructions), (2, 1));
+    assert!(string.total_construction >= Duration::from_millis(10));
+    assert!(string.interceptor_time >= Duration::from_millis(5));
+    assert!(string.interceptor_time < string.total_construction);
+
+    assert_eq!((failing.resolutions, failing.constructions), (2, 2));
+
+    assert_eq!((number.resolutions, number.constructions), (2, 1));
+    assert_eq!(number.max_construction, number.total_construction);
+    assert_eq!(number.interceptor_time, Duration::ZERO);
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use common::Log;
+use rudi::{bind, BindFunc, Binder, Implements};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Metadata, Subscriber,
+};
+
+/// collects the names of the spans with their first field
+#[derive(Clone, Default)]
+struct SpanCollector {
+    spans: Log,
+    next_id: Arc<AtomicU64>,
+}
+
+struct FirstField(Option<String>);
+
+impl Visit for FirstField {
+    fn record_debug(&mut self, _: &Field, value: &dyn std::fmt::Debug) {
+        if self.0.is_none() {
+            self.0 = Some(format!("{:?}", value));
+        }
+    }
+}
+
+impl Subscriber for SpanCollector {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        let mut field = FirstField(None);
+        span.record(&mut field);
+        self.spans.push(format!(
+            "{} {}",
+            span.metadata().name(),
+            field.0.unwrap_or_default()
+        ));
+        span::Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+fn app_module(binder: &mut Binder) {
+    bind!(binder, u32).to_singleton(1).as_eager();
+    bind!(binder, String).to_constructor(|n: u32| n.to_string());
+}
+
+#[test]
+fn tracing_test() {
+    let collector = SpanCollector::default();
+
+    tracing::subscriber::with_default(collector.clone(), || {
+        let mut im = Implements::new();
+        im.add_bind(BindFunc(app_module));
+
+        let i = im.new_injector(vec![]);
+        assert_eq!(i.get_instance::<String>().unwrap(), "1");
+    });
+
+    assert_eq!(
+        collector.spans.events(),
+        vec![
+            String::from("new_injector []"),
+            String::from("configure rudi::module::BindFunc<tracing_test::app_module>"),
+            String::from("eager u32"),
+            String::from("resolve u32"),
+            String::from("provide u32"),
+            String::from("resolve alloc::string::String"),
+            String::from("provide alloc::string::String"),
+            String::from("resolve u32"),
+        ]
+    );
+}
@@ -0,0 +1,94 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use rudi::{bind, BindFunc, Binder, Implements, InjectError, Injector, TryProvider};
+
+#[derive(Debug)]
+struct ConfigError(String);
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "config error: {}", self.0)
+    }
+}
+
+impl Error for ConfigError {}
+
+#[derive(Clone)]
+struct Config {
+    port: u16,
+}
+
+#[derive(Clone)]
+struct Server {
+    port: u16,
+}
+
+static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+
+fn load_config(path: String) -> Result<Config, ConfigError> {
+    if ATTEMPTS.fetch_add(1, Ordering::SeqCst) == 0 {
+        Err(ConfigError(format!("{} not found", path)))
+    } else {
+        Ok(Config { port: 8080 })
+    }
+}
+
+fn server_module(binder: &mut Binder) {
+    bind!(binder, String).to_singleton("app.toml".into());
+    bind!(binder, Config).to_try_constructor(load_config);
+    bind!(binder, Server).to_constructor(|c: Config| Server { port: c.port });
+}
+
+#[test]
+fn try_constructor_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(server_module));
+
+    let i = im.new_injector(vec![]);
+
+    let err = i.try_get_instance::<Server>().err().unwrap();
+    match &err {
+        InjectError::ProviderFailed {
+            type_name, path, ..
+        } => {
+            assert_eq!(type_name, "try_test::Config");
+            assert_eq!(path, "try_test::Server -> try_test::Config");
+        }
+        _ => panic!("unexpected error {}", err),
+    }
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "config error: app.toml not found"
+    );
+
+    let s = i.try_get_instance::<Server>().unwrap();
+    assert_eq!(s.port, 8080);
+}
+
+struct PortProvider;
+
+impl TryProvider for PortProvider {
+    type Provided = u16;
+    type Error = std::num::ParseIntError;
+
+    fn try_provide(&self, _injector: &Injector) -> Result<u16, Self::Error> {
+        "http".parse()
+    }
+}
+
+#[test]
+fn try_provider_test() {
+    let mut im = Implements::new();
+    im.add_bind(BindFunc(|binder: &mut Binder| {
+        bind!(binder, u16).to_try_provider(PortProvider);
+    }));
+
+    let i = im.new_injector(vec![]);
+
+    let err = i.try_get_instance::<u16>().err().unwrap();
+    assert!(matches!(err, InjectError::ProviderFailed { .. }));
+}